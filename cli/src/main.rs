@@ -1,4 +1,4 @@
-use clap::Parser;
+use clap::{Parser, Subcommand};
 use colored::Colorize;
 use indicatif::{
     ProgressBar,
@@ -15,17 +15,26 @@ use scenario_rs::{
             RemoteSudoLifecycle,
             RollbackLifecycle,
             RollbackStepLifecycle,
+            SessionLifecycle,
             SftpCopyLifecycle,
             StepsLifecycle,
         },
+        errors::PlaceholderResolutionError,
         remote_sudo::RemoteSudo,
         sftp_copy::SftpCopy,
         task::Task,
         Scenario,
     },
 };
-use std::{fs::File, io::Read, path::PathBuf, process};
-use tracing::{debug, error, info, warn};
+use std::{
+    collections::{BTreeMap, HashMap},
+    fs::File,
+    io::Read,
+    path::PathBuf,
+    process,
+    time::Duration,
+};
+use tracing::{debug, error, info, info_span, warn};
 use tracing_subscriber::FmtSubscriber;
 
 #[derive(Parser, Debug)]
@@ -33,6 +42,28 @@ use tracing_subscriber::FmtSubscriber;
 struct Cli {
     #[arg(short, long, value_name = "JSON_FILE")]
     config_path: PathBuf,
+    #[arg(short, long = "label", value_name = "KEY=VALUE", value_parser = parse_key_value)]
+    labels: Vec<(String, String)>,
+    #[arg(short, long = "import-var", value_name = "KEY=VALUE", value_parser = parse_key_value)]
+    import_vars: Vec<(String, String)>,
+    #[command(subcommand)]
+    command: Option<Command>,
+}
+
+#[derive(Subcommand, Debug)]
+enum Command {
+    /// Print the required variables with their current values (`<unset>` when
+    /// empty) and the resolved defined variables, flagging the ones that depend
+    /// on unset required variables, without connecting
+    Resolve,
+}
+
+fn parse_key_value(key_value: &str) -> Result<(String, String), String> {
+    match key_value.split_once('=') {
+        Some((key, value)) if !key.trim().is_empty() =>
+            Ok((key.trim().to_string(), value.trim().to_string())),
+        _ => Err(format!("invalid value `{key_value}`, expected KEY=VALUE")),
+    }
 }
 
 const SEPARATOR: &'static str = "------------------------------------------------------------";
@@ -42,7 +73,21 @@ fn main() {
 
     let cli: Cli = Cli::parse();
 
-    let config = ScenarioConfig::try_from(cli.config_path)
+    let labels = cli.labels.iter().cloned()
+        .collect::<BTreeMap<String, String>>()
+        .iter()
+        .map(|(key, value)| format!("{key}={value}"))
+        .collect::<Vec<String>>()
+        .join(",");
+    let scenario_span = if labels.is_empty() {
+        info_span!("scenario")
+    } else {
+        info_span!("scenario", labels = %labels)
+    };
+    let _scenario_span_guard = scenario_span.enter();
+
+    let import_vars = cli.import_vars.into_iter().collect::<HashMap<String, String>>();
+    let config = ScenarioConfig::load(cli.config_path, &import_vars)
         .unwrap_or_else(|error| {
             error!("{}", SEPARATOR);
             error!("{}", error);
@@ -50,7 +95,7 @@ fn main() {
             process::exit(1);
         });
 
-    let scenario: Scenario = match Scenario::new(config) {
+    let mut scenario: Scenario = match Scenario::new(config) {
         Ok(scenario) => scenario,
         Err(error) => {
             error!("{}", SEPARATOR);
@@ -60,6 +105,21 @@ fn main() {
         }
     };
 
+    scenario.labels().extend(cli.labels);
+
+    if let Some(Command::Resolve) = cli.command {
+        match resolve_variables(&mut scenario) {
+            Ok(output) => output.lines().for_each(|line| info!("{}", line)),
+            Err(error) => {
+                error!("{}", SEPARATOR);
+                error!("Variable resolution failed: {}", error);
+                error!("{}", SEPARATOR);
+                process::exit(1);
+            }
+        }
+        return;
+    }
+
     let lifecycle = execution_lifecycle();
 
     match scenario.execute_with_lifecycle(lifecycle) {
@@ -77,21 +137,83 @@ fn main() {
     }
 }
 
+fn resolve_variables(scenario: &mut Scenario) -> Result<String, PlaceholderResolutionError> {
+    let variables = scenario.variables();
+    let defined = variables.defined_without_required()?
+        .into_iter()
+        .collect::<BTreeMap<String, String>>();
+
+    let mut unset_required = Vec::new();
+    let mut lines = vec![SEPARATOR.to_string(), "Required variables:".to_string()];
+    for required_variable in variables.required().iter_mut() {
+        let name = required_variable.name().to_string();
+        let label = required_variable.label().to_string();
+        let value = required_variable.value();
+        if value.is_empty() {
+            lines.push(format!("{name} ({label}) = <unset>"));
+            unset_required.push(name);
+        } else {
+            lines.push(format!("{name} ({label}) = {value}"));
+        }
+    }
+    lines.push(SEPARATOR.to_string());
+    lines.push("Defined variables:".to_string());
+    for (name, value) in &defined {
+        let missing = unset_required.iter()
+            .filter(|required_name| value.contains(&format!("{{{required_name}}}")))
+            .map(String::as_str)
+            .collect::<Vec<&str>>();
+        if missing.is_empty() {
+            lines.push(format!("{name} = {value}"));
+        } else {
+            lines.push(format!("{name} = {value} (needs unset: {})", missing.join(", ")));
+        }
+    }
+    lines.push(SEPARATOR.to_string());
+
+    Ok(lines.join("\n"))
+}
+
 fn execution_lifecycle() -> ExecutionLifecycle {
     let mut lifecycle = ExecutionLifecycle::default();
+    lifecycle.session = session_lifecycle();
     lifecycle.steps = steps_lifecycle();
     lifecycle
 }
 
+fn session_lifecycle() -> SessionLifecycle {
+    let mut lifecycle = SessionLifecycle::default();
+    lifecycle.connected = |elapsed: Duration, _| {
+        info!("{}", format!("Connected in {}ms", elapsed.as_millis()).cyan());
+    };
+    lifecycle.authenticated = |elapsed: Duration, _| {
+        info!("{}", format!("Authenticated in {}ms", elapsed.as_millis()).cyan());
+    };
+    lifecycle.banner_received = |banner: &str, _| {
+        info!("{}", SEPARATOR);
+        info!("{}", "Server banner:".yellow());
+        info!("{}", banner.trim());
+    };
+    lifecycle
+}
+
 fn steps_lifecycle() -> StepsLifecycle {
     let mut lifecycle = StepsLifecycle::default();
     lifecycle.before =
-        |index: usize, task: &Task, total_steps: usize| {
+        |index: usize, task: &Task, total_steps: usize, _| {
             let step_number: usize = index + 1;
             let description = task.description();
             info!("{}", SEPARATOR);
             info!("{}", format!("[{step_number}/{total_steps}] {description}").purple());
         };
+    lifecycle.waiting_before =
+        |_, _, delay: Duration, _| {
+            info!("{}", format!("Waiting {}ms before step...", delay.as_millis()).yellow());
+        };
+    lifecycle.waiting_after =
+        |_, _, delay: Duration, _| {
+            info!("{}", format!("Waiting {}ms after step...", delay.as_millis()).yellow());
+        };
     lifecycle.remote_sudo = remote_sudo_lifecycle();
     lifecycle.sftp_copy = sftp_copy_lifecycle();
     lifecycle.rollback = rollback_lifecycle();
@@ -100,11 +222,11 @@ fn steps_lifecycle() -> StepsLifecycle {
 
 fn remote_sudo_lifecycle() -> RemoteSudoLifecycle {
     let mut lifecycle = RemoteSudoLifecycle::default();
-    lifecycle.before = |remote_sudo: &RemoteSudo| {
+    lifecycle.before = |remote_sudo: &RemoteSudo, _| {
         info!("{}", "Executing:".yellow());
         info!("{}", &remote_sudo.command().bold());
     };
-    lifecycle.channel_established = |channel: &mut dyn Read| {
+    lifecycle.channel_established = |channel: &mut dyn Read, _| {
         let mut output = String::new();
         if (*channel).read_to_string(&mut output).is_err() {
             warn!("{}", SEPARATOR);
@@ -124,14 +246,14 @@ fn remote_sudo_lifecycle() -> RemoteSudoLifecycle {
 
 fn sftp_copy_lifecycle() -> SftpCopyLifecycle {
     let mut lifecycle = SftpCopyLifecycle::default();
-    lifecycle.before = |sftp_copy: &SftpCopy| {
+    lifecycle.before = |sftp_copy: &SftpCopy, _| {
         info!("{}", "Source:".yellow());
         info!("{}", &sftp_copy.source_path().bold());
         info!("{}", "Destination:".yellow());
         info!("{}", &sftp_copy.destination_path().bold());
     };
     lifecycle.files_ready =
-        |source_file: &File, _, pb: &ProgressBar| {
+        |source_file: &File, _, pb: &ProgressBar, _| {
             if let Ok(metadata) = source_file.metadata() {
                 pb.set_length(metadata.len());
                 pb.set_draw_target(ProgressDrawTarget::stderr());
@@ -150,7 +272,7 @@ fn sftp_copy_lifecycle() -> SftpCopyLifecycle {
 fn rollback_lifecycle() -> RollbackLifecycle {
     let mut lifecycle = RollbackLifecycle::default();
     lifecycle.before =
-        |rollback_steps: &RollbackSteps| {
+        |rollback_steps: &RollbackSteps, _| {
             if rollback_steps.is_empty() {
                 info!("{}", SEPARATOR);
                 info!("[{}] No rollback actions found", "rollback".red());
@@ -163,7 +285,7 @@ fn rollback_lifecycle() -> RollbackLifecycle {
 fn rollback_step_lifecycle() -> RollbackStepLifecycle {
     let mut lifecycle = RollbackStepLifecycle::default();
     lifecycle.before =
-        |index: usize, rollback_task: &Task, total_rollback_steps: usize| {
+        |index: usize, rollback_task: &Task, total_rollback_steps: usize, _| {
             let task_number = index + 1;
             let description = rollback_task.description();
             info!("{}", SEPARATOR);
@@ -171,3 +293,89 @@ fn rollback_step_lifecycle() -> RollbackStepLifecycle {
         };
     lifecycle
 }
+
+#[cfg(test)]
+mod tests {
+    use super::{parse_key_value, resolve_variables};
+    use scenario_rs::scenario::{errors::PlaceholderResolutionError, Scenario};
+    use serde_json::{json, Value};
+
+    fn scenario(defined: Value) -> Scenario {
+        let config = serde_json::from_value(json!({
+            "credentials": { "username": "deployer" },
+            "server": { "host": "localhost" },
+            "execute": { "steps": [] },
+            "variables": {
+                "required": { "env": "Environment", "region": "Region" },
+                "special": {},
+                "defined": defined
+            },
+            "tasks": {}
+        })).expect("test scenario config should be valid");
+        Scenario::new(config).expect("test scenario should be created")
+    }
+
+    #[test]
+    fn parse_key_value_splits_on_first_equals_sign() {
+        assert_eq!(
+            parse_key_value(" build = 42=a "),
+            Ok(("build".to_string(), "42=a".to_string())),
+        );
+    }
+
+    #[test]
+    fn parse_key_value_rejects_missing_key_or_separator() {
+        assert!(parse_key_value("build").is_err());
+        assert!(parse_key_value("=42").is_err());
+    }
+
+    #[test]
+    fn resolve_variables_prints_required_and_nested_defined_values() {
+        let mut scenario = scenario(json!({
+            "path": "{base}/x",
+            "base": "{root}-{username}",
+            "root": "/opt"
+        }));
+        *scenario.variables().required()[0].value() = "prod".to_string();
+
+        let output = resolve_variables(&mut scenario).unwrap();
+
+        assert!(output.contains("env (Environment) = prod"), "{output}");
+        assert!(output.contains("region (Region) = <unset>"), "{output}");
+        assert!(output.contains("base = /opt-deployer\n"), "{output}");
+        assert!(output.contains("path = /opt-deployer/x\n"), "{output}");
+        assert!(output.contains("root = /opt\n"), "{output}");
+        assert!(!output.contains("\nenv ="), "{output}");
+        assert!(!output.contains("\nregion ="), "{output}");
+    }
+
+    #[test]
+    fn resolve_variables_flags_defined_values_that_need_unset_required_variables() {
+        let mut scenario = scenario(json!({
+            "deploy_path": "{base}/{region}",
+            "base": "/opt/{env}"
+        }));
+        *scenario.variables().required()[0].value() = "prod".to_string();
+
+        let output = resolve_variables(&mut scenario).unwrap();
+
+        assert!(output.contains("base = /opt/prod\n"), "{output}");
+        assert!(output.contains("deploy_path = /opt/prod/{region} (needs unset: region)\n"), "{output}");
+    }
+
+    #[test]
+    fn resolve_variables_reports_unresolvable_placeholders() {
+        let mut scenario = scenario(json!({
+            "path": "{base}/x",
+            "base": "{missing}"
+        }));
+
+        let error = resolve_variables(&mut scenario).unwrap_err();
+
+        match error {
+            PlaceholderResolutionError::CannotResolveVariablesPlaceholders(keys) =>
+                assert_eq!(keys, vec!["base".to_string(), "path".to_string()]),
+            error => panic!("unexpected error: {error}"),
+        }
+    }
+}
@@ -1,11 +1,14 @@
 use crate::scenario::errors::ScenarioConfigError;
+use regex::{Captures, Regex};
 use serde::Deserialize;
+use serde_json::Value;
 use std::collections::BTreeMap;
 use std::{
     collections::HashMap,
+    env,
     fs::File,
     ops::{Deref, DerefMut},
-    path::PathBuf,
+    path::{Path, PathBuf},
 };
 
 #[derive(Deserialize, Clone, Debug)]
@@ -15,20 +18,112 @@ pub struct ScenarioConfig {
     pub execute: ExecuteConfig,
     pub variables: VariablesConfig,
     pub tasks: TasksConfig,
+    pub require_remote: Option<RequireRemoteConfig>,
 }
 
 impl TryFrom<PathBuf> for ScenarioConfig {
     type Error = ScenarioConfigError;
 
     fn try_from(value: PathBuf) -> Result<Self, Self::Error> {
-        let config_file: File = File::open(value)
-            .map_err(ScenarioConfigError::CannotOpenFile)?;
-        let config: ScenarioConfig = serde_json::from_reader(config_file)
+        ScenarioConfig::load(value, &HashMap::new())
+    }
+}
+
+impl ScenarioConfig {
+    pub fn load(
+        path: PathBuf,
+        import_values: &HashMap<String, String>,
+    ) -> Result<Self, ScenarioConfigError> {
+        Self::load_with_env(path, import_values, &|name| env::var(name).ok())
+    }
+
+    fn load_with_env(
+        path: PathBuf,
+        import_values: &HashMap<String, String>,
+        env_var: &dyn Fn(&str) -> Option<String>,
+    ) -> Result<Self, ScenarioConfigError> {
+        let config = load_config_json(&path, import_values, env_var, &mut Vec::new())?;
+        let config: ScenarioConfig = serde_json::from_value(config)
             .map_err(ScenarioConfigError::CannotReadJson)?;
         Ok(config)
     }
 }
 
+fn load_config_json(
+    path: &Path,
+    import_values: &HashMap<String, String>,
+    env_var: &dyn Fn(&str) -> Option<String>,
+    imported: &mut Vec<PathBuf>,
+) -> Result<Value, ScenarioConfigError> {
+    let path = path.canonicalize()
+        .map_err(ScenarioConfigError::CannotOpenFile)?;
+    if imported.contains(&path) {
+        return Err(ScenarioConfigError::CircularImport(path));
+    }
+    imported.push(path.clone());
+
+    let config_file: File = File::open(&path)
+        .map_err(ScenarioConfigError::CannotOpenFile)?;
+    let mut config: Value = serde_json::from_reader(config_file)
+        .map_err(ScenarioConfigError::CannotReadJson)?;
+
+    let parent = config.as_object_mut().and_then(|config| config.remove("parent"));
+    let Some(parent) = parent else {
+        return Ok(config);
+    };
+    let Value::String(parent) = parent else {
+        return Err(ScenarioConfigError::InvalidImportPath(parent.to_string()));
+    };
+
+    let parent_path = resolve_import_path(&parent, import_values, env_var)?;
+    let parent_path = match path.parent() {
+        Some(config_dir) => config_dir.join(parent_path),
+        None => parent_path,
+    };
+    let mut merged_config = load_config_json(&parent_path, import_values, env_var, imported)?;
+    merge_config_json(&mut merged_config, config);
+    Ok(merged_config)
+}
+
+fn resolve_import_path(
+    import_path: &str,
+    import_values: &HashMap<String, String>,
+    env_var: &dyn Fn(&str) -> Option<String>,
+) -> Result<PathBuf, ScenarioConfigError> {
+    let placeholder_regex = Regex::new(r"\{(\w+)}")
+        .expect("`placeholder_regex` should be a valid regex");
+    let mut unresolved = Vec::new();
+    let resolved = placeholder_regex.replace_all(import_path, |captures: &Captures| {
+        let name = &captures[1];
+        import_values.get(name).cloned()
+            .or_else(|| env_var(name))
+            .unwrap_or_else(|| {
+                unresolved.push(name.to_string());
+                captures[0].to_string()
+            })
+    });
+    if !unresolved.is_empty() {
+        return Err(ScenarioConfigError::CannotResolveImportPath(import_path.to_string(), unresolved));
+    }
+    Ok(PathBuf::from(resolved.into_owned()))
+}
+
+fn merge_config_json(base: &mut Value, overrides: Value) {
+    match (base, overrides) {
+        (Value::Object(base), Value::Object(overrides)) => {
+            for (key, value) in overrides {
+                match base.get_mut(&key) {
+                    Some(base_value) => merge_config_json(base_value, value),
+                    None => {
+                        base.insert(key, value);
+                    }
+                }
+            }
+        }
+        (base, overrides) => *base = overrides,
+    }
+}
+
 #[derive(Deserialize, Clone, Debug)]
 pub struct CredentialsConfig {
     pub username: String,
@@ -41,6 +136,12 @@ pub struct ServerConfig {
     pub port: Option<String>,
 }
 
+#[derive(Deserialize, Clone, Debug)]
+pub struct RequireRemoteConfig {
+    pub arch: Option<String>,
+    pub os: Option<String>,
+}
+
 #[derive(Deserialize, Clone, Debug)]
 pub struct ExecuteConfig {
     pub steps: StepsConfig,
@@ -66,6 +167,8 @@ impl DerefMut for StepsConfig {
 pub struct StepConfig {
     pub task: String,
     pub rollback: Option<RollbackStepsConfig>,
+    pub delay_before_ms: Option<u64>,
+    pub delay_after_ms: Option<u64>,
 }
 
 #[derive(Deserialize, Clone, Debug)]
@@ -181,4 +284,156 @@ pub struct RemoteSudoConfig {
 pub struct SftpCopyConfig {
     pub source_path: String,
     pub destination_path: String,
+    pub transfer: Option<TransferMethodConfig>,
+    pub verify_size: Option<bool>,
+}
+
+#[derive(Deserialize, Clone, Debug)]
+#[serde(rename_all = "lowercase")]
+pub enum TransferMethodConfig {
+    Sftp,
+    Scp,
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        config::{ScenarioConfig, TaskConfig, TransferMethodConfig},
+        scenario::errors::ScenarioConfigError,
+    };
+    use serde_json::json;
+    use std::{
+        collections::HashMap,
+        fs,
+        path::{Path, PathBuf},
+    };
+    use tempfile::tempdir;
+
+    fn write_json(path: &Path, value: serde_json::Value) {
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(path, value.to_string()).unwrap();
+    }
+
+    fn base_config(host: &str) -> serde_json::Value {
+        json!({
+            "credentials": { "username": "deployer" },
+            "server": { "host": host, "port": "2222" },
+            "execute": { "steps": [] },
+            "variables": { "required": {}, "special": {}, "defined": { "service": "base" } },
+            "tasks": {}
+        })
+    }
+
+    fn example_config() -> ScenarioConfig {
+        let path = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("../example-scenario.json");
+        ScenarioConfig::try_from(path).expect("example scenario config should be valid")
+    }
+
+    #[test]
+    fn example_config_documents_step_delays() {
+        let config = example_config();
+
+        let start_service = config.execute.steps.last().unwrap();
+        assert_eq!(start_service.delay_before_ms, Some(1000));
+        assert_eq!(start_service.delay_after_ms, Some(5000));
+    }
+
+    #[test]
+    fn example_config_documents_sftp_copy_options() {
+        let config = example_config();
+
+        match config.tasks.get("copy_jar_to_server") {
+            Some(TaskConfig::SftpCopy { sftp_copy, .. }) => {
+                assert!(matches!(sftp_copy.transfer, Some(TransferMethodConfig::Sftp)));
+                assert_eq!(sftp_copy.verify_size, Some(true));
+            }
+            other => panic!("expected an SftpCopy task, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn example_config_documents_require_remote() {
+        let require_remote = example_config().require_remote
+            .expect("example config should declare require_remote");
+
+        assert_eq!(require_remote.arch.as_deref(), Some("x86_64"));
+        assert_eq!(require_remote.os.as_deref(), Some("Linux"));
+    }
+
+    #[test]
+    fn load_resolves_env_var_in_parent_path() {
+        let dir = tempdir().unwrap();
+        write_json(&dir.path().join("staging/base.json"), base_config("staging.example.com"));
+        write_json(&dir.path().join("prod/base.json"), base_config("prod.example.com"));
+        write_json(&dir.path().join("deploy.json"), json!({
+            "parent": "{SCENARIO_RS_TEST_PARENT_ENV}/base.json",
+            "variables": { "defined": { "service": "api" } }
+        }));
+        let env_var = |name: &str| (name == "SCENARIO_RS_TEST_PARENT_ENV").then(|| "staging".to_string());
+
+        let config = ScenarioConfig::load_with_env(dir.path().join("deploy.json"), &HashMap::new(), &env_var)
+            .unwrap();
+
+        assert_eq!(config.server.host, "staging.example.com");
+        assert_eq!(config.server.port.as_deref(), Some("2222"));
+        assert_eq!(config.credentials.username, "deployer");
+        assert_eq!(config.variables.defined.get("service").map(String::as_str), Some("api"));
+    }
+
+    #[test]
+    fn load_prefers_provided_values_over_env_vars() {
+        let dir = tempdir().unwrap();
+        write_json(&dir.path().join("staging/base.json"), base_config("staging.example.com"));
+        write_json(&dir.path().join("prod/base.json"), base_config("prod.example.com"));
+        write_json(&dir.path().join("deploy.json"), json!({
+            "parent": "{SCENARIO_RS_TEST_OVERRIDE_ENV}/base.json"
+        }));
+        let env_var = |name: &str| (name == "SCENARIO_RS_TEST_OVERRIDE_ENV").then(|| "staging".to_string());
+        let import_values = HashMap::from([
+            ("SCENARIO_RS_TEST_OVERRIDE_ENV".to_string(), "prod".to_string()),
+        ]);
+
+        let config = ScenarioConfig::load_with_env(dir.path().join("deploy.json"), &import_values, &env_var)
+            .unwrap();
+
+        assert_eq!(config.server.host, "prod.example.com");
+    }
+
+    #[test]
+    fn load_rejects_templated_self_import() {
+        let dir = tempdir().unwrap();
+        write_json(&dir.path().join("deploy.json"), json!({ "parent": "{name}.json" }));
+        let import_values = HashMap::from([("name".to_string(), "deploy".to_string())]);
+
+        let result = ScenarioConfig::load(dir.path().join("deploy.json"), &import_values);
+
+        assert!(matches!(result, Err(ScenarioConfigError::CircularImport(_))));
+    }
+
+    #[test]
+    fn load_rejects_circular_imports() {
+        let dir = tempdir().unwrap();
+        write_json(&dir.path().join("a.json"), json!({ "parent": "b.json" }));
+        write_json(&dir.path().join("b.json"), json!({ "parent": "a.json" }));
+
+        let result = ScenarioConfig::load(dir.path().join("a.json"), &HashMap::new());
+
+        assert!(matches!(result, Err(ScenarioConfigError::CircularImport(_))));
+    }
+
+    #[test]
+    fn load_reports_unresolved_import_placeholders() {
+        let dir = tempdir().unwrap();
+        write_json(&dir.path().join("deploy.json"), json!({
+            "parent": "{SCENARIO_RS_TEST_UNSET_ENV}/base.json"
+        }));
+
+        let result = ScenarioConfig::load_with_env(dir.path().join("deploy.json"), &HashMap::new(), &|_| None);
+
+        match result {
+            Err(ScenarioConfigError::CannotResolveImportPath(_, unresolved)) =>
+                assert_eq!(unresolved, vec!["SCENARIO_RS_TEST_UNSET_ENV"]),
+            other => panic!("expected CannotResolveImportPath, got {other:?}"),
+        }
+    }
 }
@@ -7,19 +7,23 @@ use crate::{
 };
 use credentials::Credentials;
 use errors::ScenarioError;
-use lifecycle::ExecutionLifecycle;
+use lifecycle::{ExecutionLifecycle, SessionLifecycle};
+use require_remote::RequireRemote;
 use server::Server;
-use ssh2::Session;
-use std::net::TcpStream;
+use session::Session;
+use std::{collections::BTreeMap, net::TcpStream, time::Instant};
 use variables::Variables;
 
 pub mod credentials;
 pub mod errors;
 pub mod lifecycle;
 pub mod server;
+pub mod session;
 pub mod utils;
 pub mod variables;
+pub mod remote_command;
 pub mod remote_sudo;
+pub mod require_remote;
 pub mod execute;
 pub mod sftp_copy;
 pub mod step;
@@ -28,18 +32,27 @@ pub mod task;
 pub mod tasks;
 pub mod rollback;
 
+#[cfg(test)]
+pub(crate) mod test_utils;
+
 #[derive(Debug)]
 pub struct Scenario {
     pub(crate) server: Server,
     pub(crate) credentials: Credentials,
     pub(crate) execute: Execute,
     pub(crate) variables: Variables,
+    pub(crate) require_remote: RequireRemote,
+    pub(crate) labels: BTreeMap<String, String>,
 }
 
 impl Scenario {
     pub fn variables(&mut self) -> &mut Variables {
         &mut self.variables
     }
+
+    pub fn labels(&mut self) -> &mut BTreeMap<String, String> {
+        &mut self.labels
+    }
 }
 
 impl Scenario {
@@ -51,11 +64,16 @@ impl Scenario {
         let execute = Execute::try_from((&tasks, &config.execute))
             .map_err(ScenarioError::CannotCreateExecuteFromConfig)?;
         let variables = Variables::from(&config.variables);
+        let require_remote = config.require_remote.as_ref()
+            .map(RequireRemote::from)
+            .unwrap_or_default();
         let scenario = Scenario {
             server,
             credentials,
             execute,
             variables,
+            require_remote,
+            labels: BTreeMap::new(),
         };
         Ok(scenario)
     }
@@ -68,28 +86,75 @@ impl Scenario {
         &self,
         mut lifecycle: ExecutionLifecycle,
     ) -> Result<(), ScenarioError> {
-        (lifecycle.before)(&self);
+        (lifecycle.before)(&self, &self.labels);
+
+        let session: Session = self.new_session_with_lifecycle(&mut lifecycle.session)?;
+
+        self.execute_steps(&session, &mut lifecycle)
+    }
+
+    pub fn execute_in_session(
+        &self,
+        session: &Session,
+        mut lifecycle: ExecutionLifecycle,
+    ) -> Result<(), ScenarioError> {
+        (lifecycle.before)(self, &self.labels);
 
-        let session: Session = self.new_session()?;
+        self.execute_steps(session, &mut lifecycle)
+    }
+
+    fn execute_steps(
+        &self,
+        session: &Session,
+        lifecycle: &mut ExecutionLifecycle,
+    ) -> Result<(), ScenarioError> {
+        self.require_remote.check(session)
+            .map_err(ScenarioError::CannotVerifyRemoteEnvironment)?;
 
-        self.execute.steps.execute(&session, &self.variables, &mut lifecycle.steps)
+        self.execute.steps.execute(session, &self.variables, &self.labels, &mut lifecycle.steps)
             .map_err(ScenarioError::CannotExecuteSteps)?;
 
         Ok(())
     }
 
     pub fn new_session(&self) -> Result<Session, ScenarioError> {
+        self.new_session_with_lifecycle(&mut SessionLifecycle::default())
+    }
+
+    pub fn new_session_with_lifecycle(
+        &self,
+        lifecycle: &mut SessionLifecycle,
+    ) -> Result<Session, ScenarioError> {
+        let connect_started = Instant::now();
         let host = &self.server.host;
         let port: &str = &self.server.port;
         let tcp = TcpStream::connect(&format!("{host}:{port}"))
             .map_err(ScenarioError::CannotConnectToRemoteServer)?;
 
-        let mut session = Session::new()
+        let mut session = ssh2::Session::new()
             .map_err(ScenarioError::CannotCreateANewSession)?;
         session.set_tcp_stream(tcp);
+
+        let mut session = Session::from(session);
+        self.establish_session(&mut session, connect_started, lifecycle)?;
+
+        Ok(session)
+    }
+
+    pub(crate) fn establish_session(
+        &self,
+        session: &mut Session,
+        connect_started: Instant,
+        lifecycle: &mut SessionLifecycle,
+    ) -> Result<(), ScenarioError> {
         session.handshake()
             .map_err(ScenarioError::CannotInitiateTheSshHandshake)?;
 
+        (lifecycle.connected)(connect_started.elapsed(), &self.labels);
+
+        self.report_banner(session, lifecycle);
+
+        let auth_started = Instant::now();
         let username = &self.credentials.username;
 
         match &self.credentials.password {
@@ -99,6 +164,117 @@ impl Scenario {
                 .map_err(ScenarioError::CannotAuthenticateWithAgent)?
         }
 
-        Ok(session)
+        (lifecycle.authenticated)(auth_started.elapsed(), &self.labels);
+
+        Ok(())
+    }
+
+    fn report_banner(&self, session: &Session, lifecycle: &mut SessionLifecycle) {
+        if let Some(banner) = session.banner() {
+            (lifecycle.banner_received)(banner, &self.labels);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::scenario::{
+        lifecycle::SessionLifecycle,
+        session::Session,
+        test_utils::{scenario, scenario_config, MockChannel, MockSftp},
+    };
+    use std::{
+        cell::RefCell,
+        collections::BTreeMap,
+        time::{Duration, Instant},
+    };
+
+    thread_local! {
+        static BANNERS: RefCell<Vec<String>> = const { RefCell::new(Vec::new()) };
+        static MILESTONES: RefCell<Vec<(&'static str, Option<Duration>)>> = const { RefCell::new(Vec::new()) };
+        static SESSION_LABELS: RefCell<Vec<BTreeMap<String, String>>> = const { RefCell::new(Vec::new()) };
+    }
+
+    #[test]
+    fn establish_session_reports_connected_then_authenticated() {
+        let scenario = scenario(scenario_config());
+        let mut session = Session::test(MockChannel::default(), MockSftp::default())
+            .with_banner("SSH-2.0-OpenSSH_9.6");
+        let mut lifecycle = SessionLifecycle {
+            connected: |elapsed, _| MILESTONES.with(|milestones| {
+                milestones.borrow_mut().push(("connected", Some(elapsed)))
+            }),
+            banner_received: |_, _| MILESTONES.with(|milestones| {
+                milestones.borrow_mut().push(("banner_received", None))
+            }),
+            authenticated: |elapsed, _| MILESTONES.with(|milestones| {
+                milestones.borrow_mut().push(("authenticated", Some(elapsed)))
+            }),
+        };
+        let connect_started = Instant::now() - Duration::from_millis(25);
+
+        scenario.establish_session(&mut session, connect_started, &mut lifecycle)
+            .expect("test session should be established");
+
+        MILESTONES.with(|milestones| {
+            let milestones = milestones.borrow();
+            let names = milestones.iter().map(|(name, _)| *name).collect::<Vec<_>>();
+            assert_eq!(names, vec!["connected", "banner_received", "authenticated"]);
+            let connected = milestones[0].1.expect("connected should carry its timing");
+            let authenticated = milestones[2].1.expect("authenticated should carry its timing");
+            assert!(connected >= Duration::from_millis(25));
+            assert!(authenticated < connected);
+        });
+    }
+
+    #[test]
+    fn report_banner_emits_banner_provided_by_session() {
+        let session = Session::test(MockChannel::default(), MockSftp::default())
+            .with_banner("SSH-2.0-OpenSSH_9.6 Authorized use only");
+        let mut lifecycle = SessionLifecycle {
+            banner_received: |banner, _| BANNERS.with(|banners| banners.borrow_mut().push(banner.to_string())),
+            ..Default::default()
+        };
+
+        scenario(scenario_config()).report_banner(&session, &mut lifecycle);
+
+        BANNERS.with(|banners| assert_eq!(
+            *banners.borrow(),
+            vec!["SSH-2.0-OpenSSH_9.6 Authorized use only".to_string()],
+        ));
+    }
+
+    #[test]
+    fn report_banner_skips_session_without_banner() {
+        let session = Session::test(MockChannel::default(), MockSftp::default());
+        let mut lifecycle = SessionLifecycle {
+            banner_received: |banner, _| BANNERS.with(|banners| banners.borrow_mut().push(banner.to_string())),
+            ..Default::default()
+        };
+
+        scenario(scenario_config()).report_banner(&session, &mut lifecycle);
+
+        BANNERS.with(|banners| assert!(banners.borrow().is_empty()));
+    }
+
+    #[test]
+    fn establish_session_passes_scenario_labels_to_session_events() {
+        let mut scenario = scenario(scenario_config());
+        scenario.labels().insert("build".to_string(), "42".to_string());
+        let mut session = Session::test(MockChannel::default(), MockSftp::default())
+            .with_banner("SSH-2.0-OpenSSH_9.6");
+        let mut lifecycle = SessionLifecycle {
+            connected: |_, labels| SESSION_LABELS.with(|session_labels| session_labels.borrow_mut().push(labels.clone())),
+            banner_received: |_, labels| SESSION_LABELS.with(|session_labels| session_labels.borrow_mut().push(labels.clone())),
+            authenticated: |_, labels| SESSION_LABELS.with(|session_labels| session_labels.borrow_mut().push(labels.clone())),
+        };
+
+        scenario.establish_session(&mut session, Instant::now(), &mut lifecycle)
+            .expect("test session should be established");
+
+        let expected = BTreeMap::from([("build".to_string(), "42".to_string())]);
+        SESSION_LABELS.with(|session_labels| {
+            assert_eq!(*session_labels.borrow(), vec![expected.clone(), expected.clone(), expected]);
+        });
     }
 }
@@ -1,3 +1,4 @@
+use std::path::PathBuf;
 use thiserror::Error;
 
 #[derive(Error, Debug)]
@@ -6,6 +7,12 @@ pub enum ScenarioConfigError {
     CannotOpenFile(#[source] std::io::Error),
     #[error("Cannot read JSON config file: {0}")]
     CannotReadJson(#[source] serde_json::Error),
+    #[error("Config `parent` must be a path string, found: {0}")]
+    InvalidImportPath(String),
+    #[error("Cannot resolve placeholders {1:?} in import path: {0}")]
+    CannotResolveImportPath(String, Vec<String>),
+    #[error("Circular config import: {0}")]
+    CircularImport(PathBuf),
 }
 
 #[derive(Error, Debug)]
@@ -22,10 +29,24 @@ pub enum ScenarioError {
     CannotAuthenticateWithPassword(#[source] ssh2::Error),
     #[error("Cannot authenticate with ssh-agent: {0}")]
     CannotAuthenticateWithAgent(#[source] ssh2::Error),
+    #[error("Cannot verify remote environment: {0}")]
+    CannotVerifyRemoteEnvironment(#[source] RequireRemoteError),
     #[error("Cannot execute steps: {0}")]
     CannotExecuteSteps(#[source] StepsError),
 }
 
+#[derive(Error, Debug)]
+pub enum RequireRemoteError {
+    #[error("Cannot execute remote command: {0}")]
+    CannotExecuteRemoteCommand(#[source] RemoteCommandError),
+    #[error("Remote environment mismatch for `{command}`: expected {expected}, found {actual}")]
+    RemoteEnvironmentMismatch {
+        command: String,
+        expected: String,
+        actual: String,
+    },
+}
+
 #[derive(Error, Debug)]
 pub enum ExecuteError {
     #[error("Cannot create Steps from config: {0}")]
@@ -74,30 +95,56 @@ pub enum TaskError {
 
 #[derive(Error, Debug)]
 pub enum RemoteSudoError {
+    #[error("Cannot execute remote command: {0}")]
+    CannotExecuteRemoteCommand(#[source] RemoteCommandError),
+    #[error("Cannot resolve placeholders in command: {0}")]
+    CannotResolveCommandPlaceholders(#[source] PlaceholderResolutionError),
+}
+
+#[derive(Error, Debug)]
+pub enum RemoteCommandError {
     #[error("Cannot establish a session channel: {0}")]
     CannotEstablishSessionChannel(#[source] ssh2::Error),
     #[error("Cannot execute remote command: {0}")]
     CannotExecuteRemoteCommand(#[source] ssh2::Error),
+    #[error("Cannot read remote command output: {0}")]
+    CannotReadRemoteCommandOutput(#[source] std::io::Error),
+    #[error("Cannot wait for the remote command channel to close: {0}")]
+    CannotWaitForChannelClose(#[source] ssh2::Error),
     #[error("Cannot obtain exit status of remote command: {0}")]
     CannotObtainRemoteCommandExitStatus(#[source] ssh2::Error),
     #[error("Remote command failed with status code: {0}")]
     RemoteCommandFailedWithStatusCode(i32),
-    #[error("Cannot resolve placeholders in command: {0}")]
-    CannotResolveCommandPlaceholders(#[source] PlaceholderResolutionError),
 }
 
 #[derive(Error, Debug)]
 pub enum SftpCopyError {
     #[error("Cannot open a channel and initialize the SFTP subsystem: {0}")]
     CannotOpenChannelAndInitializeSftp(#[source] ssh2::Error),
+    #[error("Cannot open an SCP channel: {0}")]
+    CannotOpenScpChannel(#[source] ssh2::Error),
+    #[error("Cannot finish the SCP transfer: {0}")]
+    CannotFinishScpTransfer(#[source] ssh2::Error),
     #[error("Cannot open source file: {0}")]
     CannotOpenSourceFile(#[source] std::io::Error),
+    #[error("Cannot read source file metadata: {0}")]
+    CannotReadSourceFileMetadata(#[source] std::io::Error),
     #[error("Cannot create a destination file: {0}")]
     CannotCreateDestinationFile(#[source] ssh2::Error),
     #[error("Cannot read from source file: {0}")]
     CannotReadSourceFile(#[source] std::io::Error),
     #[error("Cannot write to destination file: {0}")]
     CannotWriteDestinationFile(#[source] std::io::Error),
+    #[error("Cannot stat destination file: {0}")]
+    CannotStatDestinationFile(#[source] ssh2::Error),
+    #[error("Cannot query destination file size: {0}")]
+    CannotQueryDestinationFileSize(#[source] RemoteCommandError),
+    #[error("Cannot determine destination file size")]
+    CannotDetermineDestinationFileSize,
+    #[error("Cannot parse destination file size from remote output: {0:?}")]
+    CannotParseDestinationFileSize(String),
+    #[error("Destination file size mismatch: expected {expected} bytes, found {actual} bytes")]
+    SizeMismatch { expected: u64, actual: u64 },
     #[error("Cannot resolve placeholders in source file: {0}")]
     CannotResolveSourcePathPlaceholders(#[source] PlaceholderResolutionError),
     #[error("Cannot resolve placeholders in destination file: {0}")]
@@ -7,26 +7,48 @@ use crate::scenario::{
 };
 use indicatif::ProgressBar;
 use std::{
+    collections::BTreeMap,
     fs::File,
     io::{Read, Write},
+    time::Duration,
 };
 
 pub struct ExecutionLifecycle {
-    pub before: fn(scenario: &Scenario),
+    pub before: fn(scenario: &Scenario, labels: &BTreeMap<String, String>),
+    pub session: SessionLifecycle,
     pub steps: StepsLifecycle,
 }
 
 impl Default for ExecutionLifecycle {
     fn default() -> Self {
         ExecutionLifecycle {
-            before: |_| {},
+            before: |_, _| {},
+            session: Default::default(),
             steps: Default::default(),
         }
     }
 }
 
+pub struct SessionLifecycle {
+    pub connected: fn(elapsed: Duration, labels: &BTreeMap<String, String>),
+    pub banner_received: fn(banner: &str, labels: &BTreeMap<String, String>),
+    pub authenticated: fn(elapsed: Duration, labels: &BTreeMap<String, String>),
+}
+
+impl Default for SessionLifecycle {
+    fn default() -> Self {
+        SessionLifecycle {
+            connected: |_, _| {},
+            banner_received: |_, _| {},
+            authenticated: |_, _| {},
+        }
+    }
+}
+
 pub struct StepsLifecycle {
-    pub before: fn(index: usize, task: &Task, total_steps: usize),
+    pub before: fn(index: usize, task: &Task, total_steps: usize, labels: &BTreeMap<String, String>),
+    pub waiting_before: fn(index: usize, task: &Task, delay: Duration, labels: &BTreeMap<String, String>),
+    pub waiting_after: fn(index: usize, task: &Task, delay: Duration, labels: &BTreeMap<String, String>),
     pub remote_sudo: RemoteSudoLifecycle,
     pub sftp_copy: SftpCopyLifecycle,
     pub rollback: RollbackLifecycle,
@@ -35,7 +57,9 @@ pub struct StepsLifecycle {
 impl Default for StepsLifecycle {
     fn default() -> Self {
         StepsLifecycle {
-            before: |_, _, _| {},
+            before: |_, _, _, _| {},
+            waiting_before: |_, _, _, _| {},
+            waiting_after: |_, _, _, _| {},
             remote_sudo: Default::default(),
             sftp_copy: Default::default(),
             rollback: Default::default(),
@@ -44,21 +68,26 @@ impl Default for StepsLifecycle {
 }
 
 pub struct RollbackLifecycle {
-    pub before: fn(rollback_steps: &RollbackSteps),
+    pub before: fn(rollback_steps: &RollbackSteps, labels: &BTreeMap<String, String>),
     pub step: RollbackStepLifecycle,
 }
 
 impl Default for RollbackLifecycle {
     fn default() -> Self {
         RollbackLifecycle {
-            before: |_| {},
+            before: |_, _| {},
             step: Default::default(),
         }
     }
 }
 
 pub struct RollbackStepLifecycle {
-    pub before: fn(index: usize, rollback_task: &Task, total_rollback_steps: usize),
+    pub before: fn(
+        index: usize,
+        rollback_task: &Task,
+        total_rollback_steps: usize,
+        labels: &BTreeMap<String, String>,
+    ),
     pub remote_sudo: RemoteSudoLifecycle,
     pub sftp_copy: SftpCopyLifecycle,
 }
@@ -66,7 +95,7 @@ pub struct RollbackStepLifecycle {
 impl Default for RollbackStepLifecycle {
     fn default() -> Self {
         RollbackStepLifecycle {
-            before: |_, _, _| {},
+            before: |_, _, _, _| {},
             remote_sudo: Default::default(),
             sftp_copy: Default::default(),
         }
@@ -74,31 +103,36 @@ impl Default for RollbackStepLifecycle {
 }
 
 pub struct RemoteSudoLifecycle {
-    pub before: fn(remote_sudo: &RemoteSudo),
-    pub channel_established: fn(channel_reader: &mut dyn Read),
+    pub before: fn(remote_sudo: &RemoteSudo, labels: &BTreeMap<String, String>),
+    pub channel_established: fn(channel_reader: &mut dyn Read, labels: &BTreeMap<String, String>),
 }
 
 impl Default for RemoteSudoLifecycle {
     fn default() -> Self {
         RemoteSudoLifecycle {
-            before: |_| {},
-            channel_established: |_| {},
+            before: |_, _| {},
+            channel_established: |_, _| {},
         }
     }
 }
 
 pub struct SftpCopyLifecycle {
-    pub before: fn(sftp_copy: &SftpCopy),
-    pub files_ready: fn(source_file: &File, destination_writer: &mut dyn Write, pb: &ProgressBar),
-    pub after: fn(),
+    pub before: fn(sftp_copy: &SftpCopy, labels: &BTreeMap<String, String>),
+    pub files_ready: fn(
+        source_file: &File,
+        destination_writer: &mut dyn Write,
+        pb: &ProgressBar,
+        labels: &BTreeMap<String, String>,
+    ),
+    pub after: fn(labels: &BTreeMap<String, String>),
 }
 
 impl Default for SftpCopyLifecycle {
     fn default() -> Self {
         SftpCopyLifecycle {
-            before: |_| {},
-            files_ready: |_, _, _| {},
-            after: || {},
+            before: |_, _| {},
+            files_ready: |_, _, _, _| {},
+            after: |_| {},
         }
     }
 }
@@ -0,0 +1,41 @@
+use crate::scenario::{
+    errors::RemoteCommandError,
+    session::{Channel, Session},
+};
+use std::io::{self, Read};
+
+pub(crate) fn exec(session: &Session, command: &str) -> Result<Box<dyn Channel>, RemoteCommandError> {
+    let mut channel: Box<dyn Channel> = session.channel_session()
+        .map_err(RemoteCommandError::CannotEstablishSessionChannel)?;
+    channel.exec(command)
+        .map_err(RemoteCommandError::CannotExecuteRemoteCommand)?;
+    Ok(channel)
+}
+
+pub(crate) fn close(channel: &mut dyn Channel) -> Result<(), RemoteCommandError> {
+    io::copy(channel, &mut io::sink())
+        .map_err(RemoteCommandError::CannotReadRemoteCommandOutput)?;
+    channel.wait_close()
+        .map_err(RemoteCommandError::CannotWaitForChannelClose)?;
+
+    let exit_status = channel.exit_status()
+        .map_err(RemoteCommandError::CannotObtainRemoteCommandExitStatus)?;
+
+    if exit_status != 0 {
+        return Err(RemoteCommandError::RemoteCommandFailedWithStatusCode(exit_status));
+    }
+
+    Ok(())
+}
+
+pub(crate) fn output(session: &Session, command: &str) -> Result<String, RemoteCommandError> {
+    let mut channel = exec(session, command)?;
+
+    let mut output = String::new();
+    channel.read_to_string(&mut output)
+        .map_err(RemoteCommandError::CannotReadRemoteCommandOutput)?;
+
+    close(channel.as_mut())?;
+
+    Ok(output)
+}
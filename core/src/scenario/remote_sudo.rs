@@ -3,10 +3,12 @@ use crate::{
     scenario::{
         errors::RemoteSudoError,
         lifecycle::RemoteSudoLifecycle,
+        remote_command,
+        session::Session,
         variables::Variables,
     },
 };
-use ssh2::{Channel, Session};
+use std::collections::BTreeMap;
 
 #[derive(Debug, Clone)]
 pub struct RemoteSudo {
@@ -28,25 +30,20 @@ impl RemoteSudo {
         &self,
         session: &Session,
         variables: &Variables,
+        labels: &BTreeMap<String, String>,
         lifecycle: &mut RemoteSudoLifecycle,
     ) -> Result<(), RemoteSudoError> {
-        (lifecycle.before)(&self);
+        (lifecycle.before)(&self, labels);
 
-        let mut channel: Channel = session.channel_session()
-            .map_err(RemoteSudoError::CannotEstablishSessionChannel)?;
         let command = variables.resolve_placeholders(&self.command)
             .map_err(RemoteSudoError::CannotResolveCommandPlaceholders)?;
-        channel.exec(&format!("{command}"))
+        let mut channel = remote_command::exec(session, &command)
             .map_err(RemoteSudoError::CannotExecuteRemoteCommand)?;
 
-        (lifecycle.channel_established)(&mut channel);
+        (lifecycle.channel_established)(&mut channel, labels);
 
-        let exit_status = channel.exit_status()
-            .map_err(RemoteSudoError::CannotObtainRemoteCommandExitStatus)?;
-
-        if exit_status != 0 {
-            return Err(RemoteSudoError::RemoteCommandFailedWithStatusCode(exit_status));
-        }
+        remote_command::close(channel.as_mut())
+            .map_err(RemoteSudoError::CannotExecuteRemoteCommand)?;
 
         Ok(())
     }
@@ -0,0 +1,114 @@
+use crate::{
+    config::RequireRemoteConfig,
+    scenario::{
+        errors::RequireRemoteError,
+        remote_command,
+        session::Session,
+    },
+};
+
+#[derive(Debug, Clone, Default)]
+pub struct RequireRemote {
+    pub(crate) arch: Option<String>,
+    pub(crate) os: Option<String>,
+}
+
+impl From<&RequireRemoteConfig> for RequireRemote {
+    fn from(config: &RequireRemoteConfig) -> Self {
+        RequireRemote {
+            arch: config.arch.clone(),
+            os: config.os.clone(),
+        }
+    }
+}
+
+impl RequireRemote {
+    pub fn arch(&self) -> Option<&str> {
+        self.arch.as_deref()
+    }
+
+    pub fn os(&self) -> Option<&str> {
+        self.os.as_deref()
+    }
+
+    pub(crate) fn check(&self, session: &Session) -> Result<(), RequireRemoteError> {
+        if let Some(arch) = &self.arch {
+            Self::expect(session, "uname -m", arch)?;
+        }
+        if let Some(os) = &self.os {
+            Self::expect(session, "uname -s", os)?;
+        }
+        Ok(())
+    }
+
+    fn expect(session: &Session, command: &str, expected: &str) -> Result<(), RequireRemoteError> {
+        let output = remote_command::output(session, command)
+            .map_err(RequireRemoteError::CannotExecuteRemoteCommand)?;
+
+        let actual = output.trim();
+        if !actual.eq_ignore_ascii_case(expected.trim()) {
+            return Err(RequireRemoteError::RemoteEnvironmentMismatch {
+                command: command.to_string(),
+                expected: expected.to_string(),
+                actual: actual.to_string(),
+            });
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::scenario::{
+        errors::{RequireRemoteError, ScenarioError},
+        lifecycle::ExecutionLifecycle,
+        session::Session,
+        test_utils::{scenario, scenario_config, MockChannel, MockSftp},
+    };
+    use serde_json::json;
+
+    #[test]
+    fn execute_aborts_before_steps_when_remote_arch_mismatches() {
+        let mut config = scenario_config();
+        config["require_remote"] = json!({ "arch": "x86_64", "os": "Linux" });
+        let scenario = scenario(config);
+        let channel = MockChannel::default()
+            .with_output("uname -m", "aarch64\n")
+            .with_output("uname -s", "Linux\n");
+        let session = Session::test(channel.clone(), MockSftp::default());
+
+        let result = scenario.execute_in_session(&session, ExecutionLifecycle::default());
+
+        match result {
+            Err(ScenarioError::CannotVerifyRemoteEnvironment(
+                RequireRemoteError::RemoteEnvironmentMismatch { command, expected, actual },
+            )) => {
+                assert_eq!(command, "uname -m");
+                assert_eq!(expected, "x86_64");
+                assert_eq!(actual, "aarch64");
+            }
+            other => panic!("expected RemoteEnvironmentMismatch, got {other:?}"),
+        }
+        assert_eq!(*channel.commands.borrow(), vec!["uname -m"]);
+    }
+
+    #[test]
+    fn execute_runs_steps_when_remote_environment_matches() {
+        let mut config = scenario_config();
+        config["require_remote"] = json!({ "arch": "x86_64", "os": "linux" });
+        let scenario = scenario(config);
+        let channel = MockChannel::default()
+            .with_output("uname -m", "x86_64\n")
+            .with_output("uname -s", "Linux\n");
+        let session = Session::test(channel.clone(), MockSftp::default());
+
+        scenario.execute_in_session(&session, ExecutionLifecycle::default())
+            .expect("scenario should succeed");
+
+        assert_eq!(
+            *channel.commands.borrow(),
+            vec!["uname -m", "uname -s", "echo hello deployer"],
+        );
+    }
+}
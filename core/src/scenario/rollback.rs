@@ -4,10 +4,13 @@ use crate::scenario::variables::Variables;
 use crate::scenario::{
     errors::RollbackError,
     lifecycle::RollbackLifecycle,
+    session::Session,
     task::Task,
 };
-use ssh2::Session;
-use std::ops::{Deref, DerefMut};
+use std::{
+    collections::BTreeMap,
+    ops::{Deref, DerefMut},
+};
 
 #[derive(Debug)]
 pub struct RollbackSteps(Vec<Task>);
@@ -51,18 +54,19 @@ impl RollbackSteps {
         &self,
         session: &Session,
         variables: &Variables,
+        labels: &BTreeMap<String, String>,
         lifecycle: &mut RollbackLifecycle,
     ) -> Result<(), RollbackError> {
-        (lifecycle.before)(&self);
+        (lifecycle.before)(&self, labels);
 
         for (index, rollback_task) in self.iter().enumerate() {
-            (lifecycle.step.before)(index, rollback_task, self.len());
+            (lifecycle.step.before)(index, rollback_task, self.len(), labels);
             match rollback_task {
                 Task::RemoteSudo { remote_sudo, .. } =>
-                    remote_sudo.execute(&session, variables, &mut lifecycle.step.remote_sudo)
+                    remote_sudo.execute(&session, variables, labels, &mut lifecycle.step.remote_sudo)
                         .map_err(RollbackError::CannotRollbackRemoteSudo)?,
                 Task::SftpCopy { sftp_copy, .. } =>
-                    sftp_copy.execute(&session, variables, &mut lifecycle.step.sftp_copy)
+                    sftp_copy.execute(&session, variables, labels, &mut lifecycle.step.sftp_copy)
                         .map_err(RollbackError::CannotRollbackSftpCopy)?
             }
         }
@@ -0,0 +1,271 @@
+use std::{
+    io::{Read, Write},
+    path::Path,
+};
+
+pub trait Channel: Read {
+    fn exec(&mut self, command: &str) -> Result<(), ssh2::Error>;
+    fn exit_status(&self) -> Result<i32, ssh2::Error>;
+    fn wait_close(&mut self) -> Result<(), ssh2::Error>;
+}
+
+impl Channel for ssh2::Channel {
+    fn exec(&mut self, command: &str) -> Result<(), ssh2::Error> {
+        ssh2::Channel::exec(self, command)
+    }
+
+    fn exit_status(&self) -> Result<i32, ssh2::Error> {
+        ssh2::Channel::exit_status(self)
+    }
+
+    fn wait_close(&mut self) -> Result<(), ssh2::Error> {
+        ssh2::Channel::wait_close(self)
+    }
+}
+
+pub trait Sftp {
+    fn create(&self, path: &Path) -> Result<Box<dyn Write>, ssh2::Error>;
+    fn stat(&self, path: &Path) -> Result<ssh2::FileStat, ssh2::Error>;
+}
+
+impl Sftp for ssh2::Sftp {
+    fn create(&self, path: &Path) -> Result<Box<dyn Write>, ssh2::Error> {
+        ssh2::Sftp::create(self, path).map(|file| Box::new(file) as Box<dyn Write>)
+    }
+
+    fn stat(&self, path: &Path) -> Result<ssh2::FileStat, ssh2::Error> {
+        ssh2::Sftp::stat(self, path)
+    }
+}
+
+pub trait ScpChannel: Write {
+    fn finish(&mut self) -> Result<(), ssh2::Error>;
+}
+
+impl ScpChannel for ssh2::Channel {
+    fn finish(&mut self) -> Result<(), ssh2::Error> {
+        self.send_eof()?;
+        self.wait_eof()?;
+        self.close()?;
+        self.wait_close()
+    }
+}
+
+pub trait Scp {
+    fn send(&self, path: &Path, mode: i32, size: u64) -> Result<Box<dyn ScpChannel>, ssh2::Error>;
+}
+
+impl Scp for ssh2::Session {
+    fn send(&self, path: &Path, mode: i32, size: u64) -> Result<Box<dyn ScpChannel>, ssh2::Error> {
+        self.scp_send(path, mode, size, None)
+            .map(|channel| Box::new(channel) as Box<dyn ScpChannel>)
+    }
+}
+
+pub(crate) enum SessionType {
+    Ssh2(ssh2::Session),
+    #[cfg(any(test, feature = "test-session"))]
+    Test {
+        channel: Box<dyn Fn() -> Box<dyn Channel>>,
+        sftp: Box<dyn Fn() -> Box<dyn Sftp>>,
+        scp: Option<Box<dyn Scp>>,
+        banner: Option<String>,
+    },
+}
+
+pub struct Session {
+    pub(crate) session_type: SessionType,
+}
+
+impl From<ssh2::Session> for Session {
+    fn from(session: ssh2::Session) -> Self {
+        Session { session_type: SessionType::Ssh2(session) }
+    }
+}
+
+impl Session {
+    /// Builds a session backed by the given [`Channel`] and [`Sftp`]
+    /// implementations instead of a real SSH connection. Every call to
+    /// [`Session::channel_session`] and [`Session::sftp`] hands out a clone.
+    ///
+    /// ```
+    /// use scenario_rs::{
+    ///     config::ScenarioConfig,
+    ///     scenario::{
+    ///         lifecycle::ExecutionLifecycle,
+    ///         session::{Channel, Session, Sftp},
+    ///         Scenario,
+    ///     },
+    /// };
+    /// use std::{
+    ///     cell::RefCell,
+    ///     io::{self, Read, Write},
+    ///     path::Path,
+    ///     rc::Rc,
+    /// };
+    ///
+    /// #[derive(Clone, Default)]
+    /// struct RecordingChannel {
+    ///     commands: Rc<RefCell<Vec<String>>>,
+    /// }
+    ///
+    /// impl Read for RecordingChannel {
+    ///     fn read(&mut self, _buf: &mut [u8]) -> io::Result<usize> {
+    ///         Ok(0)
+    ///     }
+    /// }
+    ///
+    /// impl Channel for RecordingChannel {
+    ///     fn exec(&mut self, command: &str) -> Result<(), ssh2::Error> {
+    ///         self.commands.borrow_mut().push(command.to_string());
+    ///         Ok(())
+    ///     }
+    ///
+    ///     fn exit_status(&self) -> Result<i32, ssh2::Error> {
+    ///         Ok(0)
+    ///     }
+    ///
+    ///     fn wait_close(&mut self) -> Result<(), ssh2::Error> {
+    ///         Ok(())
+    ///     }
+    /// }
+    ///
+    /// #[derive(Clone)]
+    /// struct NoopSftp;
+    ///
+    /// impl Sftp for NoopSftp {
+    ///     fn create(&self, _path: &Path) -> Result<Box<dyn Write>, ssh2::Error> {
+    ///         Ok(Box::new(io::sink()))
+    ///     }
+    ///
+    ///     fn stat(&self, _path: &Path) -> Result<ssh2::FileStat, ssh2::Error> {
+    ///         Ok(ssh2::FileStat { size: None, uid: None, gid: None, perm: None, atime: None, mtime: None })
+    ///     }
+    /// }
+    ///
+    /// let config: ScenarioConfig = serde_json::from_str(r#"{
+    ///     "credentials": { "username": "deployer" },
+    ///     "server": { "host": "localhost" },
+    ///     "execute": { "steps": [{ "task": "greet" }] },
+    ///     "variables": { "required": {}, "special": {}, "defined": {} },
+    ///     "tasks": {
+    ///         "greet": {
+    ///             "type": "RemoteSudo",
+    ///             "description": "Greet",
+    ///             "error_message": "Cannot greet",
+    ///             "command": "echo hello {username}"
+    ///         }
+    ///     }
+    /// }"#).unwrap();
+    /// let scenario = Scenario::new(config).unwrap();
+    ///
+    /// let channel = RecordingChannel::default();
+    /// let session = Session::test(channel.clone(), NoopSftp);
+    ///
+    /// scenario.execute_in_session(&session, ExecutionLifecycle::default()).unwrap();
+    ///
+    /// assert_eq!(*channel.commands.borrow(), vec!["echo hello deployer"]);
+    /// ```
+    #[cfg(any(test, feature = "test-session"))]
+    pub fn test<C, S>(channel: C, sftp: S) -> Session
+    where
+        C: Channel + Clone + 'static,
+        S: Sftp + Clone + 'static,
+    {
+        Session {
+            session_type: SessionType::Test {
+                channel: Box::new(move || Box::new(channel.clone())),
+                sftp: Box::new(move || Box::new(sftp.clone())),
+                scp: None,
+                banner: None,
+            },
+        }
+    }
+
+    #[cfg(any(test, feature = "test-session"))]
+    pub fn with_scp<P>(mut self, scp: P) -> Session
+    where
+        P: Scp + 'static,
+    {
+        if let SessionType::Test { scp: test_scp, .. } = &mut self.session_type {
+            *test_scp = Some(Box::new(scp));
+        }
+        self
+    }
+
+    #[cfg(any(test, feature = "test-session"))]
+    pub fn with_banner(mut self, banner: &str) -> Session {
+        if let SessionType::Test { banner: test_banner, .. } = &mut self.session_type {
+            *test_banner = Some(banner.to_string());
+        }
+        self
+    }
+
+    pub fn banner(&self) -> Option<&str> {
+        match &self.session_type {
+            SessionType::Ssh2(session) => session.banner(),
+            #[cfg(any(test, feature = "test-session"))]
+            SessionType::Test { banner, .. } => banner.as_deref(),
+        }
+    }
+
+    pub fn handshake(&mut self) -> Result<(), ssh2::Error> {
+        match &mut self.session_type {
+            SessionType::Ssh2(session) => session.handshake(),
+            #[cfg(any(test, feature = "test-session"))]
+            SessionType::Test { .. } => Ok(()),
+        }
+    }
+
+    pub fn userauth_password(&self, username: &str, password: &str) -> Result<(), ssh2::Error> {
+        match &self.session_type {
+            SessionType::Ssh2(session) => session.userauth_password(username, password),
+            #[cfg(any(test, feature = "test-session"))]
+            SessionType::Test { .. } => Ok(()),
+        }
+    }
+
+    pub fn userauth_agent(&self, username: &str) -> Result<(), ssh2::Error> {
+        match &self.session_type {
+            SessionType::Ssh2(session) => session.userauth_agent(username),
+            #[cfg(any(test, feature = "test-session"))]
+            SessionType::Test { .. } => Ok(()),
+        }
+    }
+
+    pub fn channel_session(&self) -> Result<Box<dyn Channel>, ssh2::Error> {
+        match &self.session_type {
+            SessionType::Ssh2(session) => session.channel_session()
+                .map(|channel| Box::new(channel) as Box<dyn Channel>),
+            #[cfg(any(test, feature = "test-session"))]
+            SessionType::Test { channel, .. } => Ok(channel()),
+        }
+    }
+
+    pub fn sftp(&self) -> Result<Box<dyn Sftp>, ssh2::Error> {
+        match &self.session_type {
+            SessionType::Ssh2(session) => session.sftp()
+                .map(|sftp| Box::new(sftp) as Box<dyn Sftp>),
+            #[cfg(any(test, feature = "test-session"))]
+            SessionType::Test { sftp, .. } => Ok(sftp()),
+        }
+    }
+
+    pub fn scp_send(
+        &self,
+        path: &Path,
+        mode: i32,
+        size: u64,
+    ) -> Result<Box<dyn ScpChannel>, ssh2::Error> {
+        match &self.session_type {
+            SessionType::Ssh2(session) => Scp::send(session, path, mode, size),
+            #[cfg(any(test, feature = "test-session"))]
+            SessionType::Test { scp: Some(scp), .. } => scp.send(path, mode, size),
+            #[cfg(any(test, feature = "test-session"))]
+            SessionType::Test { scp: None, .. } => Err(ssh2::Error::new(
+                ssh2::ErrorCode::Session(-1),
+                "SCP is not configured for the test session",
+            )),
+        }
+    }
+}
@@ -1,15 +1,17 @@
 use crate::{
-    config::SftpCopyConfig,
+    config::{SftpCopyConfig, TransferMethodConfig},
     scenario::{
         errors::SftpCopyError,
         lifecycle::SftpCopyLifecycle,
+        remote_command,
+        session::Session,
         variables::Variables,
     },
 };
 use indicatif::ProgressBar;
-use ssh2::Session;
 use std::{
-    fs::File,
+    collections::BTreeMap,
+    fs::{File, Metadata},
     io::{Read, Write},
     path::Path,
 };
@@ -18,6 +20,24 @@ use std::{
 pub struct SftpCopy {
     pub(crate) source_path: String,
     pub(crate) destination_path: String,
+    pub(crate) transfer: TransferMethod,
+    pub(crate) verify_size: bool,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum TransferMethod {
+    #[default]
+    Sftp,
+    Scp,
+}
+
+impl From<&TransferMethodConfig> for TransferMethod {
+    fn from(config: &TransferMethodConfig) -> Self {
+        match config {
+            TransferMethodConfig::Sftp => TransferMethod::Sftp,
+            TransferMethodConfig::Scp => TransferMethod::Scp,
+        }
+    }
 }
 
 impl From<&SftpCopyConfig> for SftpCopy {
@@ -25,6 +45,10 @@ impl From<&SftpCopyConfig> for SftpCopy {
         SftpCopy {
             source_path: config.source_path.clone(),
             destination_path: config.destination_path.clone(),
+            transfer: config.transfer.as_ref()
+                .map(TransferMethod::from)
+                .unwrap_or_default(),
+            verify_size: config.verify_size.unwrap_or(false),
         }
     }
 }
@@ -38,16 +62,22 @@ impl SftpCopy {
         &self.destination_path
     }
 
+    pub fn transfer(&self) -> TransferMethod {
+        self.transfer
+    }
+
+    pub fn verify_size(&self) -> bool {
+        self.verify_size
+    }
+
     pub(crate) fn execute(
         &self,
         session: &Session,
         variables: &Variables,
+        labels: &BTreeMap<String, String>,
         lifecycle: &mut SftpCopyLifecycle,
     ) -> Result<(), SftpCopyError> {
-        (lifecycle.before)(&self);
-
-        let sftp = session.sftp()
-            .map_err(SftpCopyError::CannotOpenChannelAndInitializeSftp)?;
+        (lifecycle.before)(&self, labels);
 
         let source_path = variables.resolve_placeholders(&self.source_path)
             .map_err(SftpCopyError::CannotResolveSourcePathPlaceholders)?;
@@ -55,25 +85,316 @@ impl SftpCopy {
             .map_err(SftpCopyError::CannotResolveDestinationPathPlaceholders)?;
         let mut source_file = File::open(source_path)
             .map_err(SftpCopyError::CannotOpenSourceFile)?;
-        let mut destination_file = sftp.create(Path::new(&destination_path))
-            .map_err(SftpCopyError::CannotCreateDestinationFile)?;
 
         let pb = ProgressBar::hidden();
 
-        (lifecycle.files_ready)(&source_file, &mut destination_file, &pb);
+        let copied_bytes = match self.transfer {
+            TransferMethod::Sftp => {
+                let sftp = session.sftp()
+                    .map_err(SftpCopyError::CannotOpenChannelAndInitializeSftp)?;
+                let mut destination_file = sftp.create(Path::new(&destination_path))
+                    .map_err(SftpCopyError::CannotCreateDestinationFile)?;
+                Self::copy(&mut source_file, &mut destination_file, labels, lifecycle, &pb)?
+            }
+            TransferMethod::Scp => {
+                let metadata = source_file.metadata()
+                    .map_err(SftpCopyError::CannotReadSourceFileMetadata)?;
+                let mode = source_file_mode(&metadata);
+                let mut destination_channel = session
+                    .scp_send(Path::new(&destination_path), mode, metadata.len())
+                    .map_err(SftpCopyError::CannotOpenScpChannel)?;
+                let copied_bytes = Self::copy(&mut source_file, &mut destination_channel, labels, lifecycle, &pb)?;
+                destination_channel.finish()
+                    .map_err(SftpCopyError::CannotFinishScpTransfer)?;
+                copied_bytes
+            }
+        };
+
+        pb.finish();
+
+        if self.verify_size {
+            self.verify_destination_size(session, &destination_path, copied_bytes)?;
+        }
+
+        (lifecycle.after)(labels);
+
+        Ok(())
+    }
+
+    fn copy(
+        source_file: &mut File,
+        destination: &mut dyn Write,
+        labels: &BTreeMap<String, String>,
+        lifecycle: &mut SftpCopyLifecycle,
+        pb: &ProgressBar,
+    ) -> Result<u64, SftpCopyError> {
+        (lifecycle.files_ready)(source_file, destination, pb, labels);
 
         let mut copy_buffer = Vec::new();
 
         source_file.read_to_end(&mut copy_buffer)
             .map_err(SftpCopyError::CannotReadSourceFile)?;
 
-        pb.wrap_write(destination_file).write_all(&copy_buffer)
+        pb.wrap_write(destination).write_all(&copy_buffer)
             .map_err(SftpCopyError::CannotWriteDestinationFile)?;
 
-        pb.finish();
+        Ok(copy_buffer.len() as u64)
+    }
 
-        (lifecycle.after)();
+    fn verify_destination_size(
+        &self,
+        session: &Session,
+        destination_path: &str,
+        expected: u64,
+    ) -> Result<(), SftpCopyError> {
+        let actual = match self.transfer {
+            TransferMethod::Sftp => {
+                let sftp = session.sftp()
+                    .map_err(SftpCopyError::CannotOpenChannelAndInitializeSftp)?;
+                sftp.stat(Path::new(destination_path))
+                    .map_err(SftpCopyError::CannotStatDestinationFile)?
+                    .size
+                    .ok_or(SftpCopyError::CannotDetermineDestinationFileSize)?
+            }
+            TransferMethod::Scp => {
+                // The server may not provide the SFTP subsystem at all, so ask the
+                // remote shell for the size over a plain exec channel instead.
+                let command = format!("wc -c < '{}'", destination_path.replace('\'', "'\\''"));
+                let output = remote_command::output(session, &command)
+                    .map_err(SftpCopyError::CannotQueryDestinationFileSize)?;
+                output.trim().parse::<u64>()
+                    .map_err(|_| SftpCopyError::CannotParseDestinationFileSize(output))?
+            }
+        };
+
+        if actual != expected {
+            return Err(SftpCopyError::SizeMismatch { expected, actual });
+        }
 
         Ok(())
     }
 }
+
+#[cfg(unix)]
+fn source_file_mode(metadata: &Metadata) -> i32 {
+    use std::os::unix::fs::PermissionsExt;
+    (metadata.permissions().mode() & 0o777) as i32
+}
+
+#[cfg(not(unix))]
+fn source_file_mode(metadata: &Metadata) -> i32 {
+    if metadata.permissions().readonly() { 0o444 } else { 0o644 }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{SftpCopy, TransferMethod};
+    use crate::scenario::{
+        errors::SftpCopyError,
+        lifecycle::{ExecutionLifecycle, SftpCopyLifecycle},
+        session::{Scp, ScpChannel, Session},
+        test_utils::{scenario, scenario_config, MockChannel, MockSftp},
+    };
+    use serde_json::{json, Value};
+    use std::{
+        cell::RefCell,
+        collections::BTreeMap,
+        io::{self, Write},
+        path::{Path, PathBuf},
+        rc::Rc,
+    };
+    use tempfile::NamedTempFile;
+
+    #[derive(Default)]
+    struct RecordingScp {
+        sent: Rc<RefCell<Vec<(PathBuf, i32, u64)>>>,
+        written: Rc<RefCell<Vec<u8>>>,
+        finished: Rc<RefCell<bool>>,
+    }
+
+    struct RecordingScpChannel {
+        written: Rc<RefCell<Vec<u8>>>,
+        finished: Rc<RefCell<bool>>,
+    }
+
+    impl Write for RecordingScpChannel {
+        fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+            self.written.borrow_mut().extend_from_slice(buf);
+            Ok(buf.len())
+        }
+
+        fn flush(&mut self) -> io::Result<()> {
+            Ok(())
+        }
+    }
+
+    impl ScpChannel for RecordingScpChannel {
+        fn finish(&mut self) -> Result<(), ssh2::Error> {
+            *self.finished.borrow_mut() = true;
+            Ok(())
+        }
+    }
+
+    impl Scp for RecordingScp {
+        fn send(&self, path: &Path, mode: i32, size: u64) -> Result<Box<dyn ScpChannel>, ssh2::Error> {
+            self.sent.borrow_mut().push((path.to_path_buf(), mode, size));
+            Ok(Box::new(RecordingScpChannel {
+                written: self.written.clone(),
+                finished: self.finished.clone(),
+            }))
+        }
+    }
+
+    fn source_file(content: &[u8]) -> NamedTempFile {
+        let mut source_file = NamedTempFile::new().expect("temporary source file should be created");
+        source_file.write_all(content).expect("temporary source file should be written");
+        source_file
+    }
+
+    fn sftp_copy_config(source_path: &Path, sftp_copy: Value) -> Value {
+        let mut config = scenario_config();
+        let mut task = json!({
+            "type": "SftpCopy",
+            "description": "Upload",
+            "error_message": "Cannot upload",
+            "source_path": source_path.to_str().unwrap(),
+            "destination_path": "/remote/app.jar"
+        });
+        task.as_object_mut().unwrap().extend(sftp_copy.as_object().unwrap().clone());
+        config["tasks"]["upload"] = task;
+        config["execute"]["steps"] = json!([{ "task": "upload" }]);
+        config
+    }
+
+    #[test]
+    fn execute_routes_scp_transfer_through_scp_backend() {
+        let source_file = source_file(b"jar contents");
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            std::fs::set_permissions(source_file.path(), std::fs::Permissions::from_mode(0o640)).unwrap();
+        }
+        let scenario = scenario(sftp_copy_config(source_file.path(), json!({ "transfer": "scp" })));
+        let sftp = MockSftp::default();
+        let scp = RecordingScp::default();
+        let (sent, written, finished) = (scp.sent.clone(), scp.written.clone(), scp.finished.clone());
+        let session = Session::test(MockChannel::default(), sftp.clone()).with_scp(scp);
+
+        scenario.execute_in_session(&session, ExecutionLifecycle::default())
+            .expect("scenario should succeed");
+
+        #[cfg(unix)]
+        let expected_mode = 0o640;
+        #[cfg(not(unix))]
+        let expected_mode = 0o644;
+        assert_eq!(*sent.borrow(), vec![(PathBuf::from("/remote/app.jar"), expected_mode, 12)]);
+        assert_eq!(*written.borrow(), b"jar contents");
+        assert!(*finished.borrow());
+        assert!(sftp.created.borrow().is_empty());
+    }
+
+    #[test]
+    fn execute_uses_sftp_by_default() {
+        let source_file = source_file(b"jar contents");
+        let scenario = scenario(sftp_copy_config(source_file.path(), json!({})));
+        let sftp = MockSftp::default();
+        let scp = RecordingScp::default();
+        let sent = scp.sent.clone();
+        let session = Session::test(MockChannel::default(), sftp.clone()).with_scp(scp);
+
+        scenario.execute_in_session(&session, ExecutionLifecycle::default())
+            .expect("scenario should succeed");
+
+        assert_eq!(*sftp.created.borrow(), vec![PathBuf::from("/remote/app.jar")]);
+        assert_eq!(*sftp.written.borrow(), b"jar contents");
+        assert!(sent.borrow().is_empty());
+    }
+
+    fn verified_copy(source_path: &Path, transfer: TransferMethod) -> SftpCopy {
+        SftpCopy {
+            source_path: source_path.to_str().unwrap().to_string(),
+            destination_path: "/remote/app.jar".to_string(),
+            transfer,
+            verify_size: true,
+        }
+    }
+
+    fn execute(sftp_copy: &SftpCopy, session: &Session) -> Result<(), SftpCopyError> {
+        let mut scenario = scenario(scenario_config());
+        sftp_copy.execute(session, scenario.variables(), &BTreeMap::new(), &mut SftpCopyLifecycle::default())
+    }
+
+    #[test]
+    fn verify_size_accepts_matching_sftp_destination_size() {
+        let source_file = source_file(b"jar contents");
+        let sftp_copy = verified_copy(source_file.path(), TransferMethod::Sftp);
+        let session = Session::test(MockChannel::default(), MockSftp::default().with_remote_size(Some(12)));
+
+        execute(&sftp_copy, &session).expect("sizes should match");
+    }
+
+    #[test]
+    fn verify_size_rejects_mismatching_sftp_destination_size() {
+        let source_file = source_file(b"jar contents");
+        let sftp_copy = verified_copy(source_file.path(), TransferMethod::Sftp);
+        let session = Session::test(MockChannel::default(), MockSftp::default().with_remote_size(Some(7)));
+
+        let result = execute(&sftp_copy, &session);
+
+        assert!(matches!(result, Err(SftpCopyError::SizeMismatch { expected: 12, actual: 7 })));
+    }
+
+    #[test]
+    fn verify_size_fails_when_sftp_destination_size_is_unknown() {
+        let source_file = source_file(b"jar contents");
+        let sftp_copy = verified_copy(source_file.path(), TransferMethod::Sftp);
+        let session = Session::test(MockChannel::default(), MockSftp::default().with_remote_size(None));
+
+        let result = execute(&sftp_copy, &session);
+
+        assert!(matches!(result, Err(SftpCopyError::CannotDetermineDestinationFileSize)));
+    }
+
+    #[test]
+    fn verify_size_queries_scp_destination_size_over_a_channel() {
+        let source_file = source_file(b"jar contents");
+        let sftp_copy = verified_copy(source_file.path(), TransferMethod::Scp);
+        let channel = MockChannel::default().with_output("wc -c < '/remote/app.jar'", "      12\n");
+        let commands = channel.commands.clone();
+        let sftp = MockSftp::default().with_remote_size(Some(0));
+        let session = Session::test(channel, sftp).with_scp(RecordingScp::default());
+
+        execute(&sftp_copy, &session).expect("sizes should match");
+
+        assert_eq!(*commands.borrow(), vec!["wc -c < '/remote/app.jar'"]);
+    }
+
+    #[test]
+    fn verify_size_rejects_mismatching_scp_destination_size() {
+        let source_file = source_file(b"jar contents");
+        let sftp_copy = verified_copy(source_file.path(), TransferMethod::Scp);
+        let channel = MockChannel::default().with_output("wc -c < '/remote/app.jar'", "3\n");
+        let session = Session::test(channel, MockSftp::default()).with_scp(RecordingScp::default());
+
+        let result = execute(&sftp_copy, &session);
+
+        assert!(matches!(result, Err(SftpCopyError::SizeMismatch { expected: 12, actual: 3 })));
+    }
+
+    #[test]
+    fn verify_size_reports_unparsable_scp_destination_size() {
+        let source_file = source_file(b"jar contents");
+        let sftp_copy = verified_copy(source_file.path(), TransferMethod::Scp);
+        let channel = MockChannel::default()
+            .with_output("wc -c < '/remote/app.jar'", "wc: /remote/app.jar: No such file\n");
+        let session = Session::test(channel, MockSftp::default()).with_scp(RecordingScp::default());
+
+        let result = execute(&sftp_copy, &session);
+
+        match result {
+            Err(SftpCopyError::CannotParseDestinationFileSize(output)) =>
+                assert_eq!(output, "wc: /remote/app.jar: No such file\n"),
+            other => panic!("expected CannotParseDestinationFileSize, got {other:?}"),
+        }
+    }
+}
@@ -7,16 +7,19 @@ use crate::{
         errors::StepError
         ,
         lifecycle::StepsLifecycle,
+        session::Session,
         task::Task
         ,
     },
 };
-use ssh2::Session;
+use std::{collections::BTreeMap, time::Duration};
 
 #[derive(Debug)]
 pub struct Step {
     pub(crate) task: Task,
     pub(crate) rollback_steps: RollbackSteps,
+    pub(crate) delay_before: Option<Duration>,
+    pub(crate) delay_after: Option<Duration>,
 }
 
 impl TryFrom<(&Tasks, &StepConfig)> for Step {
@@ -33,6 +36,8 @@ impl TryFrom<(&Tasks, &StepConfig)> for Step {
                         .map_err(StepError::CannotCreateRollbackStepsFromConfig)?,
                 None => RollbackSteps::default()
             },
+            delay_before: step_config.delay_before_ms.map(Duration::from_millis),
+            delay_after: step_config.delay_after_ms.map(Duration::from_millis),
         })
     }
 }
@@ -42,13 +47,22 @@ impl Step {
         &self.rollback_steps
     }
 
+    pub fn delay_before(&self) -> Option<Duration> {
+        self.delay_before
+    }
+
+    pub fn delay_after(&self) -> Option<Duration> {
+        self.delay_after
+    }
+
     pub(crate) fn rollback(
         &self,
         session: &Session,
         variables: &Variables,
+        labels: &BTreeMap<String, String>,
         lifecycle: &mut StepsLifecycle,
     ) -> Result<(), StepError> {
-        self.rollback_steps.execute(session, variables, &mut lifecycle.rollback)
+        self.rollback_steps.execute(session, variables, labels, &mut lifecycle.rollback)
             .map_err(StepError::CannotExecuteRollbackSteps)
     }
 }
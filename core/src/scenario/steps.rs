@@ -4,13 +4,17 @@ use crate::{
     scenario::{
         errors::StepsError,
         lifecycle::StepsLifecycle,
+        session::Session,
         step::Step,
         task::Task,
         tasks::Tasks,
     },
 };
-use ssh2::Session;
-use std::ops::{Deref, DerefMut};
+use std::{
+    collections::BTreeMap,
+    ops::{Deref, DerefMut},
+    thread,
+};
 
 #[derive(Debug)]
 pub struct Steps(Vec<Step>);
@@ -45,29 +49,124 @@ impl Steps {
         &self,
         session: &Session,
         variables: &Variables,
+        labels: &BTreeMap<String, String>,
         mut lifecycle: &mut StepsLifecycle,
     ) -> Result<(), StepsError> {
         for (index, step) in self.iter().enumerate() {
             let task = &step.task;
-            (lifecycle.before)(index, task, self.len());
+            (lifecycle.before)(index, task, self.len(), labels);
+
+            if let Some(delay) = step.delay_before {
+                (lifecycle.waiting_before)(index, task, delay, labels);
+                thread::sleep(delay);
+            }
+
             let error_message = task.error_message().to_string();
 
             let task_result = match task {
                 Task::RemoteSudo { remote_sudo, .. } =>
-                    remote_sudo.execute(session, variables, &mut lifecycle.remote_sudo)
+                    remote_sudo.execute(session, variables, labels, &mut lifecycle.remote_sudo)
                         .map_err(|error| StepsError::CannotExecuteRemoteSudoCommand(error, error_message)),
                 Task::SftpCopy { sftp_copy, .. } =>
-                    sftp_copy.execute(session, variables, &mut lifecycle.sftp_copy)
+                    sftp_copy.execute(session, variables, labels, &mut lifecycle.sftp_copy)
                         .map_err(|error| StepsError::CannotExecuteSftpCopyCommand(error, error_message))
             };
 
             if let Err(error) = task_result {
-                step.rollback(&session, variables, &mut lifecycle)
+                step.rollback(&session, variables, labels, &mut lifecycle)
                     .map_err(StepsError::CannotRollbackStep)?;
                 return Err(error);
             };
+
+            if let Some(delay) = step.delay_after {
+                (lifecycle.waiting_after)(index, task, delay, labels);
+                thread::sleep(delay);
+            }
         }
 
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::scenario::{
+        lifecycle::{ExecutionLifecycle, StepsLifecycle},
+        session::Session,
+        task::Task,
+        test_utils::{scenario, scenario_config, MockChannel, MockSftp},
+    };
+    use serde_json::json;
+    use std::{
+        cell::RefCell,
+        collections::BTreeMap,
+        time::{Duration, Instant},
+    };
+
+    thread_local! {
+        static WAITING: RefCell<Vec<(&'static str, Duration)>> = const { RefCell::new(Vec::new()) };
+        static STEP_LABELS: RefCell<Vec<(&'static str, BTreeMap<String, String>)>> = const { RefCell::new(Vec::new()) };
+    }
+
+    fn record_labels(event: &'static str, labels: &BTreeMap<String, String>) {
+        STEP_LABELS.with(|step_labels| step_labels.borrow_mut().push((event, labels.clone())));
+    }
+
+    #[test]
+    fn execute_waits_for_configured_delays() {
+        let mut config = scenario_config();
+        config["execute"]["steps"][0]["delay_before_ms"] = json!(20);
+        config["execute"]["steps"][0]["delay_after_ms"] = json!(30);
+        let scenario = scenario(config);
+        let session = Session::test(MockChannel::default(), MockSftp::default());
+
+        let mut lifecycle = StepsLifecycle {
+            waiting_before: |_, _: &Task, delay, _| {
+                WAITING.with(|waiting| waiting.borrow_mut().push(("before", delay)));
+            },
+            waiting_after: |_, _: &Task, delay, _| {
+                WAITING.with(|waiting| waiting.borrow_mut().push(("after", delay)));
+            },
+            ..Default::default()
+        };
+
+        let started = Instant::now();
+        scenario.execute.steps.execute(&session, &scenario.variables, &scenario.labels, &mut lifecycle)
+            .expect("steps should succeed");
+
+        assert!(started.elapsed() >= Duration::from_millis(50));
+        WAITING.with(|waiting| assert_eq!(*waiting.borrow(), vec![
+            ("before", Duration::from_millis(20)),
+            ("after", Duration::from_millis(30)),
+        ]));
+    }
+
+    #[test]
+    fn execute_passes_scenario_labels_to_every_event() {
+        let mut config = scenario_config();
+        config["execute"]["steps"][0]["delay_before_ms"] = json!(1);
+        let mut scenario = scenario(config);
+        scenario.labels().insert("build".to_string(), "42".to_string());
+        let session = Session::test(MockChannel::default(), MockSftp::default());
+
+        let mut lifecycle = ExecutionLifecycle::default();
+        lifecycle.before = |_, labels| record_labels("scenario.before", labels);
+        lifecycle.steps.before = |_, _: &Task, _, labels| record_labels("steps.before", labels);
+        lifecycle.steps.waiting_before = |_, _: &Task, _, labels| record_labels("steps.waiting_before", labels);
+        lifecycle.steps.remote_sudo.before = |_, labels| record_labels("remote_sudo.before", labels);
+        lifecycle.steps.remote_sudo.channel_established =
+            |_, labels| record_labels("remote_sudo.channel_established", labels);
+
+        scenario.execute_in_session(&session, lifecycle)
+            .expect("scenario should succeed");
+
+        let labels = BTreeMap::from([("build".to_string(), "42".to_string())]);
+        STEP_LABELS.with(|step_labels| assert_eq!(*step_labels.borrow(), vec![
+            ("scenario.before", labels.clone()),
+            ("steps.before", labels.clone()),
+            ("steps.waiting_before", labels.clone()),
+            ("remote_sudo.before", labels.clone()),
+            ("remote_sudo.channel_established", labels),
+        ]));
+    }
+}
@@ -0,0 +1,121 @@
+use crate::{
+    config::ScenarioConfig,
+    scenario::{
+        session::{Channel, Sftp},
+        Scenario,
+    },
+};
+use serde_json::{json, Value};
+use std::{
+    cell::RefCell,
+    collections::HashMap,
+    io::{self, Cursor, Read, Write},
+    path::{Path, PathBuf},
+    rc::Rc,
+};
+
+pub(crate) fn scenario_config() -> Value {
+    json!({
+        "credentials": { "username": "deployer" },
+        "server": { "host": "localhost" },
+        "execute": { "steps": [{ "task": "greet" }] },
+        "variables": { "required": {}, "special": {}, "defined": {} },
+        "tasks": {
+            "greet": {
+                "type": "RemoteSudo",
+                "description": "Greet",
+                "error_message": "Cannot greet",
+                "command": "echo hello {username}"
+            }
+        }
+    })
+}
+
+pub(crate) fn scenario(config: Value) -> Scenario {
+    let config: ScenarioConfig = serde_json::from_value(config)
+        .expect("test scenario config should be valid");
+    Scenario::new(config).expect("test scenario should be created")
+}
+
+#[derive(Clone, Default)]
+pub(crate) struct MockChannel {
+    pub(crate) commands: Rc<RefCell<Vec<String>>>,
+    outputs: HashMap<String, String>,
+    output: Cursor<Vec<u8>>,
+}
+
+impl MockChannel {
+    pub(crate) fn with_output(mut self, command: &str, output: &str) -> Self {
+        self.outputs.insert(command.to_string(), output.to_string());
+        self
+    }
+}
+
+impl Read for MockChannel {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        self.output.read(buf)
+    }
+}
+
+impl Channel for MockChannel {
+    fn exec(&mut self, command: &str) -> Result<(), ssh2::Error> {
+        self.commands.borrow_mut().push(command.to_string());
+        let output = self.outputs.get(command).cloned().unwrap_or_default();
+        self.output = Cursor::new(output.into_bytes());
+        Ok(())
+    }
+
+    fn exit_status(&self) -> Result<i32, ssh2::Error> {
+        Ok(0)
+    }
+
+    fn wait_close(&mut self) -> Result<(), ssh2::Error> {
+        Ok(())
+    }
+}
+
+#[derive(Clone, Default)]
+pub(crate) struct MockSftp {
+    pub(crate) created: Rc<RefCell<Vec<PathBuf>>>,
+    pub(crate) written: Rc<RefCell<Vec<u8>>>,
+    remote_size: Option<Option<u64>>,
+}
+
+impl MockSftp {
+    /// Makes `stat` report the given size instead of the number of bytes written.
+    pub(crate) fn with_remote_size(mut self, size: Option<u64>) -> Self {
+        self.remote_size = Some(size);
+        self
+    }
+}
+
+struct SharedWriter(Rc<RefCell<Vec<u8>>>);
+
+impl Write for SharedWriter {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.0.borrow_mut().extend_from_slice(buf);
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+impl Sftp for MockSftp {
+    fn create(&self, path: &Path) -> Result<Box<dyn Write>, ssh2::Error> {
+        self.created.borrow_mut().push(path.to_path_buf());
+        Ok(Box::new(SharedWriter(self.written.clone())))
+    }
+
+    fn stat(&self, _path: &Path) -> Result<ssh2::FileStat, ssh2::Error> {
+        Ok(ssh2::FileStat {
+            size: self.remote_size.unwrap_or_else(|| Some(self.written.borrow().len() as u64)),
+            uid: None,
+            gid: None,
+            perm: None,
+            atime: None,
+            mtime: None,
+        })
+    }
+}
@@ -38,7 +38,7 @@ impl From<&VariablesConfig> for Variables {
             defined: variables_map,
         };
         variables._resolve_special_variables(&config.special);
-        dbg!(variables)
+        variables
     }
 }
 
@@ -47,26 +47,78 @@ impl Variables {
         Ok(self._resolve_placeholders()?)
     }
 
+    /// Resolves only the defined variables. Placeholders of required variables
+    /// that have no value yet are kept as they are instead of being replaced
+    /// with empty strings, so they show which input is still missing.
+    pub fn defined_without_required(&self) -> Result<HashMap<String, String>, PlaceholderResolutionError> {
+        let variables = self._variables(false);
+        let unset_required = self.required.iter()
+            .filter(|required_variable| required_variable.value.is_empty())
+            .map(|required_variable| format!("{{{}}}", required_variable.name))
+            .collect::<Vec<String>>();
+
+        let mut unresolved_keys = Vec::new();
+        let mut resolved_variables = HashMap::new();
+        for (key, value) in &self.defined {
+            let resolved_value = Self::_substitute(&variables, value);
+            let mut remaining = resolved_value.clone();
+            unset_required.iter().for_each(|placeholder| remaining = remaining.replace(placeholder, ""));
+            if remaining.has_placeholders() {
+                unresolved_keys.push(key.to_owned());
+            }
+            resolved_variables.insert(key.to_owned(), resolved_value);
+        }
+        unresolved_keys.sort();
+
+        if !unresolved_keys.is_empty() {
+            return Err(PlaceholderResolutionError::CannotResolveVariablesPlaceholders(unresolved_keys));
+        }
+
+        Ok(resolved_variables)
+    }
+
     pub fn required(&mut self) -> &mut RequiredVariables {
         &mut self.required
     }
 
     pub(crate) fn resolve_placeholders(&self, input: &str) -> Result<String, PlaceholderResolutionError> {
-        let mut output = input.to_string();
+        let output = self._substitute_placeholders(input);
+        if output.has_placeholders() {
+            return Err(PlaceholderResolutionError::CannotResolvePlaceholders(output));
+        }
+        Ok(output)
+    }
+
+    fn _substitute_placeholders(&self, input: &str) -> String {
+        Self::_substitute(&self._variables(true), input)
+    }
 
+    fn _variables(&self, include_unset_required: bool) -> HashMap<&str, &str> {
         let mut variables = self.defined.iter()
             .map(|(key, value)| (key.as_str(), value.as_str()))
             .collect::<HashMap<&str, &str>>();
-        self.required.iter().for_each(|required_variable| {
-            variables.insert(required_variable.name.as_str(), required_variable.value.as_str());
-        });
-        for (key, value) in variables {
-            output = output.replace(&format!("{{{key}}}"), value);
-        }
-        if output.has_placeholders() {
-            return Err(PlaceholderResolutionError::CannotResolvePlaceholders(output));
+        self.required.iter()
+            .filter(|required_variable| include_unset_required || !required_variable.value.is_empty())
+            .for_each(|required_variable| {
+                variables.insert(required_variable.name.as_str(), required_variable.value.as_str());
+            });
+        variables
+    }
+
+    fn _substitute(variables: &HashMap<&str, &str>, input: &str) -> String {
+        let mut output = input.to_string();
+        let max_iterations = 10;
+        for _ in 0..max_iterations {
+            let mut substituted = output.clone();
+            for (key, value) in variables {
+                substituted = substituted.replace(&format!("{{{key}}}"), value);
+            }
+            if substituted == output {
+                break;
+            }
+            output = substituted;
         }
-        Ok(output)
+        output
     }
 
     fn _resolve_special_variables(&mut self, config: &SpecialVariablesConfig) {
@@ -90,7 +142,7 @@ impl Variables {
             let mut changes = false;
             for key in &resolved_variables.keys().cloned().collect::<Vec<String>>() {
                 let value = &resolved_variables[key];
-                let new_value = self.resolve_placeholders(value)?;
+                let new_value = self._substitute_placeholders(value);
                 if new_value != resolved_variables[key] {
                     resolved_variables.insert(key.to_string(), new_value);
                     changes = true;
@@ -102,10 +154,11 @@ impl Variables {
             iterations += 1;
         }
 
-        let unresolved_keys = resolved_variables.iter()
+        let mut unresolved_keys = resolved_variables.iter()
             .filter(|(_, value)| value.has_placeholders())
             .map(|(key, _)| key.to_owned())
             .collect::<Vec<String>>();
+        unresolved_keys.sort();
 
         if !unresolved_keys.is_empty() {
             return Err(PlaceholderResolutionError::CannotResolveVariablesPlaceholders(unresolved_keys));
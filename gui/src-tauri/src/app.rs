@@ -4,7 +4,12 @@ use scenario_rs::{
     scenario::Scenario,
 };
 use serde::{Deserialize, Serialize};
-use std::{collections::HashMap, ops::Deref, path::PathBuf, str::FromStr};
+use std::{
+    collections::{BTreeMap, HashMap},
+    ops::Deref,
+    path::PathBuf,
+    str::FromStr,
+};
 use tauri::{AppHandle, Manager};
 
 #[derive(Serialize, Deserialize, Debug, Clone)]
@@ -12,6 +17,8 @@ pub struct ScenarioAppStateConfig {
     config_path: String,
     output_log: String,
     required_variables: HashMap<String, String>,
+    #[serde(default)]
+    labels: BTreeMap<String, String>,
 }
 
 impl From<&ScenarioAppState> for ScenarioAppStateConfig {
@@ -20,6 +27,7 @@ impl From<&ScenarioAppState> for ScenarioAppStateConfig {
             config_path: state.config_path.clone(),
             output_log: state.output_log.clone(),
             required_variables: state.required_variables.clone(),
+            labels: state.labels.clone(),
         }
     }
 }
@@ -27,6 +35,7 @@ impl From<&ScenarioAppState> for ScenarioAppStateConfig {
 pub struct ScenarioAppState {
     pub(crate) config_path: String,
     pub(crate) required_variables: HashMap<String, String>,
+    pub(crate) labels: BTreeMap<String, String>,
     pub(crate) output_log: String,
     pub(crate) app_handle: AppHandle,
     pub(crate) config: Option<ScenarioConfig>,
@@ -40,6 +49,7 @@ impl ScenarioAppState {
         Self {
             config_path: String::new(),
             required_variables: HashMap::new(),
+            labels: BTreeMap::new(),
             output_log: String::new(),
             app_handle: app,
             config: None,
@@ -54,6 +64,7 @@ impl ScenarioAppState {
                 self.config_path = config_path.clone();
                 self.output_log = loaded_state.output_log;
                 self.required_variables = loaded_state.required_variables;
+                self.labels = loaded_state.labels;
                 self.load_config(config_path.as_str());
             }
         }
@@ -109,7 +120,7 @@ impl ScenarioAppState {
 
         let lifecycle_handler = LifecycleHandler::try_initialize(self.app_handle.clone());
 
-        let scenario = match Scenario::new(config.clone()) {
+        let mut scenario = match Scenario::new(config.clone()) {
             Ok(scenario) => {
                 self.log_message(format!("{SEPARATOR}\nScenario loaded\n{SEPARATOR}\n"));
                 scenario
@@ -122,6 +133,8 @@ impl ScenarioAppState {
             }
         };
 
+        scenario.labels().extend(self.labels.clone());
+
         self.is_executing = true;
 
         match scenario.execute_with_lifecycle(lifecycle_handler) {
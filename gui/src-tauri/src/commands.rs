@@ -59,6 +59,18 @@ pub fn update_required_variables(
     state.required_variables = required_variables.clone();
 }
 
+#[tauri::command]
+pub fn get_labels(state: State<'_, Mutex<ScenarioAppState>>) -> BTreeMap<String, String> {
+    let state = state.lock().unwrap();
+    state.labels.clone()
+}
+
+#[tauri::command(async)]
+pub fn update_labels(labels: BTreeMap<String, String>, state: State<'_, Mutex<ScenarioAppState>>) {
+    let mut state = state.lock().unwrap();
+    state.labels = labels;
+}
+
 #[tauri::command(async)]
 pub fn execute_scenario(state: State<'_, Mutex<ScenarioAppState>>) {
     let mut state = state.lock().unwrap();
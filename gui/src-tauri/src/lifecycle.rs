@@ -2,7 +2,7 @@ use crate::{app::ScenarioAppState, shared::SEPARATOR};
 use scenario_rs::scenario::{
     lifecycle::{
         ExecutionLifecycle, RemoteSudoLifecycle, RollbackLifecycle, RollbackStepLifecycle,
-        SftpCopyLifecycle, StepsLifecycle,
+        SessionLifecycle, SftpCopyLifecycle, StepsLifecycle,
     },
     remote_sudo::RemoteSudo,
     rollback::RollbackSteps,
@@ -10,8 +10,10 @@ use scenario_rs::scenario::{
     task::Task,
 };
 use std::{
+    collections::BTreeMap,
     io::Read,
     sync::{Mutex, OnceLock},
+    time::Duration,
 };
 use tauri::{AppHandle, Manager};
 
@@ -26,6 +28,7 @@ impl LifecycleHandler {
     pub fn try_initialize(window: AppHandle) -> ExecutionLifecycle {
         LIFECYCLE_HANDLER.get_or_init(|| LifecycleHandler::new(window));
         let mut lifecycle = ExecutionLifecycle::default();
+        lifecycle.session = session_lifecycle();
         lifecycle.steps = steps_lifecycle();
         lifecycle
     }
@@ -68,10 +71,15 @@ impl LifecycleHandler {
         ));
     }
 
-    pub fn log_rollback_before(&self, rollback_steps: &RollbackSteps) {
+    pub fn log_rollback_before(
+        &self,
+        rollback_steps: &RollbackSteps,
+        labels: &BTreeMap<String, String>,
+    ) {
         if rollback_steps.is_empty() {
+            let labels = format_labels(labels);
             self.log_message(format!(
-                "{SEPARATOR}\n[rollback] No rollback actions found\n"
+                "{SEPARATOR}\n[rollback] No rollback actions found{labels}\n"
             ));
         }
     }
@@ -81,11 +89,13 @@ impl LifecycleHandler {
         index: usize,
         rollback_task: &Task,
         total_rollback_steps: usize,
+        labels: &BTreeMap<String, String>,
     ) {
         let task_number = index + 1;
         let description = rollback_task.description();
+        let labels = format_labels(labels);
         self.log_message(format!(
-            "{SEPARATOR}\n[rollback] [{task_number}/{total_rollback_steps}] {description}\n"
+            "{SEPARATOR}\n[rollback] [{task_number}/{total_rollback_steps}] {description}{labels}\n"
         ));
     }
 
@@ -97,9 +107,19 @@ impl LifecycleHandler {
     }
 }
 
+fn session_lifecycle() -> SessionLifecycle {
+    let mut lifecycle = SessionLifecycle::default();
+    lifecycle.connected = log_session_connected;
+    lifecycle.banner_received = log_session_banner_received;
+    lifecycle.authenticated = log_session_authenticated;
+    lifecycle
+}
+
 fn steps_lifecycle() -> StepsLifecycle {
     let mut lifecycle = StepsLifecycle::default();
     lifecycle.before = log_step_before;
+    lifecycle.waiting_before = log_step_waiting_before;
+    lifecycle.waiting_after = log_step_waiting_after;
     lifecycle.remote_sudo = remote_sudo_lifecycle();
     lifecycle.sftp_copy = sftp_copy_lifecycle();
     lifecycle.rollback = rollback_lifecycle();
@@ -132,42 +152,114 @@ fn rollback_step_lifecycle() -> RollbackStepLifecycle {
     lifecycle
 }
 
-pub fn log_step_before(index: usize, task: &Task, total_steps: usize) {
+pub fn log_session_connected(elapsed: Duration, labels: &BTreeMap<String, String>) {
+    if let Some(logger) = LIFECYCLE_HANDLER.get() {
+        let elapsed_ms = elapsed.as_millis();
+        let labels = format_labels(labels);
+        logger.log_message(format!("Connected in {elapsed_ms}ms{labels}\n"));
+    }
+}
+
+pub fn log_session_authenticated(elapsed: Duration, _labels: &BTreeMap<String, String>) {
+    if let Some(logger) = LIFECYCLE_HANDLER.get() {
+        let elapsed_ms = elapsed.as_millis();
+        logger.log_message(format!("Authenticated in {elapsed_ms}ms\n"));
+    }
+}
+
+pub fn log_session_banner_received(banner: &str, _labels: &BTreeMap<String, String>) {
+    if let Some(logger) = LIFECYCLE_HANDLER.get() {
+        let banner = banner.trim();
+        logger.log_message(format!("{SEPARATOR}\nServer banner:\n{banner}\n"));
+    }
+}
+
+fn format_labels(labels: &BTreeMap<String, String>) -> String {
+    if labels.is_empty() {
+        return String::new();
+    }
+    let labels = labels
+        .iter()
+        .map(|(key, value)| format!("{key}={value}"))
+        .collect::<Vec<String>>()
+        .join(",");
+    format!(" [{labels}]")
+}
+
+pub fn log_step_before(
+    index: usize,
+    task: &Task,
+    total_steps: usize,
+    labels: &BTreeMap<String, String>,
+) {
     if let Some(logger) = LIFECYCLE_HANDLER.get() {
         let task_number: usize = index + 1;
         let description = task.description();
+        let labels = format_labels(labels);
         logger.log_message(format!(
-            "{SEPARATOR}\n[{task_number}/{total_steps}] {description}\n"
+            "{SEPARATOR}\n[{task_number}/{total_steps}] {description}{labels}\n"
         ));
     }
 }
 
-pub fn log_remote_sudo_before(remote_sudo: &RemoteSudo) {
+pub fn log_step_waiting_before(
+    _index: usize,
+    _task: &Task,
+    delay: Duration,
+    _labels: &BTreeMap<String, String>,
+) {
+    if let Some(logger) = LIFECYCLE_HANDLER.get() {
+        let delay_ms = delay.as_millis();
+        logger.log_message(format!("Waiting {delay_ms}ms before step...\n"));
+    }
+}
+
+pub fn log_step_waiting_after(
+    _index: usize,
+    _task: &Task,
+    delay: Duration,
+    _labels: &BTreeMap<String, String>,
+) {
+    if let Some(logger) = LIFECYCLE_HANDLER.get() {
+        let delay_ms = delay.as_millis();
+        logger.log_message(format!("Waiting {delay_ms}ms after step...\n"));
+    }
+}
+
+pub fn log_remote_sudo_before(remote_sudo: &RemoteSudo, _labels: &BTreeMap<String, String>) {
     if let Some(logger) = LIFECYCLE_HANDLER.get() {
         logger.log_remote_sudo_before(remote_sudo);
     }
 }
 
-pub fn log_remote_sudo_channel_established(channel: &mut dyn Read) {
+pub fn log_remote_sudo_channel_established(
+    channel: &mut dyn Read,
+    _labels: &BTreeMap<String, String>,
+) {
     if let Some(logger) = LIFECYCLE_HANDLER.get() {
         logger.log_remote_sudo_channel_established(channel);
     }
 }
 
-pub fn log_sftp_copy_before(sftp_copy: &SftpCopy) {
+pub fn log_sftp_copy_before(sftp_copy: &SftpCopy, _labels: &BTreeMap<String, String>) {
     if let Some(logger) = LIFECYCLE_HANDLER.get() {
         logger.log_sftp_copy_before(sftp_copy);
     }
 }
 
-pub fn log_rollback_before(rollback_steps: &RollbackSteps) {
+pub fn log_rollback_before(rollback_steps: &RollbackSteps, labels: &BTreeMap<String, String>) {
     if let Some(logger) = LIFECYCLE_HANDLER.get() {
-        logger.log_rollback_before(rollback_steps);
+        logger.log_rollback_before(rollback_steps, labels);
     }
 }
 
-pub fn log_rollback_step_before(index: usize, rollback_task: &Task, total_rollback_steps: usize) {
+pub fn log_rollback_step_before(
+    index: usize,
+    rollback_task: &Task,
+    total_rollback_steps: usize,
+    labels: &BTreeMap<String, String>,
+) {
     if let Some(logger) = LIFECYCLE_HANDLER.get() {
-        logger.log_rollback_step_before(index, rollback_task, total_rollback_steps);
+        logger.log_rollback_step_before(index, rollback_task, total_rollback_steps, labels);
     }
 }
@@ -3,8 +3,8 @@
 
 use crate::app::ScenarioAppState;
 use commands::{
-    clear_log, execute_scenario, get_config_path, get_log, get_required_variables, load_config,
-    save_state, update_required_variables,
+    clear_log, execute_scenario, get_config_path, get_labels, get_log, get_required_variables,
+    load_config, save_state, update_labels, update_required_variables,
 };
 use std::sync::Mutex;
 use tauri::Manager;
@@ -31,6 +31,8 @@ fn main() {
             load_config,
             get_required_variables,
             update_required_variables,
+            get_labels,
+            update_labels,
             execute_scenario
         ])
         .run(tauri::generate_context!())
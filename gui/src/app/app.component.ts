@@ -36,6 +36,7 @@ interface RequiredField {
 export class AppComponent {
   executionLog = new FormControl<string>('');
   scenarioConfigPath = new FormControl<string>('');
+  labels = new FormControl<string>('');
   requiredFields: { [key: string]: RequiredField } = {};
   requiredFieldsFormGroup = new FormGroup<RequiredFieldsForm>({});
   isExecuting = signal(false);
@@ -52,6 +53,15 @@ export class AppComponent {
       .then((log) => this.executionLog.setValue(log));
     this.fetchConfigPath()
       .then(() => this.loadConfigFile());
+    invoke<{ [key: string]: string }>('get_labels')
+      .then((labels) => {
+        const formattedLabels = Object.entries(labels)
+          .map(([key, value]) => `${key}=${value}`)
+          .join(', ');
+        this.labels.setValue(formattedLabels, { emitEvent: false });
+      });
+    this.labels.valueChanges
+      .subscribe(() => this.updateLabels());
     this.requiredFieldsFormGroup.valueChanges
       .subscribe((requiredFieldsPartial) => {
         for (const name in requiredFieldsPartial) {
@@ -136,6 +146,18 @@ export class AppComponent {
     return invoke('update_required_variables', { requiredVariables })
   }
 
+  async updateLabels(): Promise<void> {
+    const labels: { [key: string]: string } = {};
+    for (const label of (this.labels.value || '').split(',')) {
+      const separatorIndex = label.indexOf('=');
+      const key = label.slice(0, separatorIndex).trim();
+      if (separatorIndex > 0 && key !== '') {
+        labels[key] = label.slice(separatorIndex + 1).trim();
+      }
+    }
+    return invoke('update_labels', { labels })
+  }
+
   executeScenario(): void {
     this.isExecuting.set(true);
     invoke('execute_scenario')